@@ -3,7 +3,7 @@ use crate::sound;
 use bevy_app::{App, AppExit, ScheduleRunnerPlugin, Startup, Update};
 use bevy_ecs::message::MessageWriter;
 use bevy_ecs::prelude::{NonSendMut, ResMut};
-use bevy_openal::{BevyOpenalPlugin, OpenalRuntime, OpenalSettings};
+use bevy_openal::{BevyOpenalPlugin, OpenalRuntime, OpenalSettings, SoundRegistry};
 use bevy_time::TimePlugin;
 use bevy_transform::prelude::TransformPlugin;
 use std::time::Duration;
@@ -14,7 +14,6 @@ pub(crate) fn run(command_receiver: cli::CommandReceiver) {
     App::new()
         .add_message::<AppExit>()
         .insert_non_send_resource(command_receiver)
-        .insert_resource(sound::BufferRegistry::default())
         .insert_resource(sound::ListenerTarget::default())
         .insert_resource(sound::OrbitState::default())
         .insert_resource(sound::LoopTracker::default())
@@ -35,6 +34,7 @@ pub(crate) fn run(command_receiver: cli::CommandReceiver) {
                 sound::apply_listener_target,
                 handle_commands,
                 sound::update_orbit,
+                sound::report_audio_events,
                 cli::ensure_prompt_visible,
             ),
         )
@@ -44,7 +44,7 @@ pub(crate) fn run(command_receiver: cli::CommandReceiver) {
 fn handle_commands(
     mut receiver: NonSendMut<cli::CommandReceiver>,
     mut runtime: Option<ResMut<OpenalRuntime>>,
-    mut registry: ResMut<sound::BufferRegistry>,
+    mut registry: ResMut<SoundRegistry>,
     mut listener_target: ResMut<sound::ListenerTarget>,
     mut orbit: ResMut<sound::OrbitState>,
     mut exit: MessageWriter<AppExit>,
@@ -4,7 +4,9 @@ use bevy_ecs::message::MessageWriter;
 use bevy_ecs::prelude::{NonSendMut, ResMut};
 use bevy_math::Vec3;
 use bevy_openal::{
-    decode_to_mono_i16, AudioRenderMode, DistanceModel, OpenalRuntime, PlayOneShotParams,
+    db_from_gain, gain_from_db, AudioBus, AudioRenderMode, BufferKey, DecodedAudioMono16,
+    DistanceModel, ImportOptions, OpenalRuntime, PlayOneShotParams, PlaybackOffset, RuntimeError,
+    SoundRegistry, VoiceFilter, VoiceId,
 };
 use shell_words::split;
 use std::io::Write;
@@ -12,8 +14,11 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 
+const BUFFER_ACK_TIMEOUT: Duration = Duration::from_secs(1);
+
 static CTRL_C_REQUESTED: AtomicBool = AtomicBool::new(false);
 static PROMPT_SHOWN: AtomicBool = AtomicBool::new(false);
 static PROMPT_ACTIVE: AtomicBool = AtomicBool::new(false);
@@ -67,17 +72,25 @@ pub(crate) fn print_help_hint() {
 
 pub(crate) fn print_help() {
     println!("Commands:");
-    println!("  help | status");
+    println!("  help | status | voices");
     println!("  quit");
     println!();
     println!("Audio settings:");
     println!("  mode <auto|stereo|hrtf|surround>");
+    println!("  mode <mono|uhj|quad|5.1|6.1|7.1|ambisonic>   (explicit layout, with fallbacks)");
+    println!("  hrtf [dataset|default]   (list or select the HRTF dataset)");
     println!("  distance <none|inverse|inverse-clamp|linear|linear-clamp|exponent|exponent-clamp>");
     println!("  mute <on|off>");
+    println!("  volume <gain|<db>dB>   (e.g. volume 0.5, volume -6dB)");
+    println!("  bus <sfx|ui|music|vo|ambience> <gain|<db>dB>   (per-bus volume)");
+    println!("  timescale <scale>      (pitch/speed of non-UI voices, e.g. 0.5)");
+    println!("  device-pause <on|off>");
     println!();
     println!("Buffers:");
-    println!("  load <name> <path>");
+    println!("  load <name> <path> [normalize] [trim] [gain <db>] [loop <start> <end>]");
+    println!("  reload <name> <path>   (swap audio under playing voices)");
     println!("  gen <name> <sine|noise> <seconds> [freq_hz]");
+    println!("  unload <name>");
     println!();
     println!("Playback:");
     println!("  play <name> [x y z] [gain] [pitch]");
@@ -85,6 +98,12 @@ pub(crate) fn print_help() {
     println!("    Note: if you include gain/pitch, supply all coords first");
     println!("  loop <name> [x y z] [gain] [pitch]");
     println!("  loop stop [name]");
+    println!("  seek <voice> <seconds>");
+    println!("  fade <voice> <gain> <seconds>");
+    println!("  stop <voice> [fade_seconds]");
+    println!("  pause <voice|all|world>");
+    println!("  resume <voice|all|world>");
+    println!("    world = every voice except the UI bus");
     println!();
     println!("Listener:");
     println!("  listener <x y z>");
@@ -106,13 +125,26 @@ pub(crate) fn parse_command(input: &str) -> Result<Command, String> {
     match head.as_str() {
         "help" | "h" => Ok(Command::Help),
         "status" => Ok(Command::Status),
+        "voices" => Ok(Command::Voices),
         "mode" => parse_mode(tail),
+        "hrtf" => Ok(parse_hrtf(tail)),
         "distance" => parse_distance(tail),
         "mute" => parse_mute(tail),
+        "volume" => parse_volume(tail),
+        "bus" => parse_bus_volume(tail),
+        "timescale" => parse_timescale(tail),
         "load" => parse_load(tail),
+        "reload" => parse_reload(tail),
+        "unload" => parse_unload(tail),
         "gen" => parse_gen(tail),
         "play" => parse_play(tail),
         "loop" => parse_loop(tail),
+        "seek" => parse_seek(tail),
+        "fade" => parse_fade(tail),
+        "stop" => parse_stop(tail),
+        "pause" => parse_pause(tail, true),
+        "resume" => parse_pause(tail, false),
+        "device-pause" => parse_device_pause(tail),
         "listener" => parse_listener(tail),
         "orbit" => parse_orbit(tail),
         "orbitv" | "orbit-vertical" => parse_orbit_vertical(tail),
@@ -147,7 +179,7 @@ impl Drop for CommandReceiver {
 
 pub(crate) struct CommandContext<'a, 'w> {
     runtime: Option<&'a OpenalRuntime>,
-    registry: &'a mut sound::BufferRegistry,
+    registry: &'a mut SoundRegistry,
     listener_target: &'a mut sound::ListenerTarget,
     orbit: &'a mut sound::OrbitState,
     receiver: &'a mut CommandReceiver,
@@ -158,7 +190,7 @@ pub(crate) struct CommandContext<'a, 'w> {
 impl<'a, 'w> CommandContext<'a, 'w> {
     pub(crate) fn new(
         runtime: Option<&'a OpenalRuntime>,
-        registry: &'a mut sound::BufferRegistry,
+        registry: &'a mut SoundRegistry,
         listener_target: &'a mut sound::ListenerTarget,
         orbit: &'a mut sound::OrbitState,
         receiver: &'a mut CommandReceiver,
@@ -189,6 +221,87 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
             };
             print_status(runtime);
         }
+        Command::Voices => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            print_voices(runtime);
+        }
+        Command::Seek { voice, seconds } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            match runtime
+                .runtime()
+                .set_voice_offset(voice, PlaybackOffset::Seconds(seconds))
+            {
+                Ok(()) => println!("Seeking voice {voice} to {seconds:.2}s"),
+                Err(err) => println!("Seek failed: {err}"),
+            }
+        }
+        Command::Fade {
+            voice,
+            gain,
+            seconds,
+        } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            match runtime
+                .runtime()
+                .fade_to(voice, gain, Duration::from_secs_f32(seconds))
+            {
+                Ok(()) => println!("Fading voice {voice} to {gain:.2} over {seconds:.2}s"),
+                Err(err) => println!("Fade failed: {err}"),
+            }
+        }
+        Command::Stop {
+            voice,
+            fade_seconds,
+        } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            match runtime
+                .runtime()
+                .stop_voice(voice, Duration::from_secs_f32(fade_seconds))
+            {
+                Ok(()) => println!("Stopping voice {voice} over {fade_seconds:.2}s"),
+                Err(err) => println!("Stop failed: {err}"),
+            }
+        }
+        Command::Pause { target, paused } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            let runtime = runtime.runtime();
+            let result = match (target, paused) {
+                (PauseTarget::Voice(voice), true) => runtime.pause_voice(voice),
+                (PauseTarget::Voice(voice), false) => runtime.resume_voice(voice),
+                (PauseTarget::Voices(filter), true) => runtime.pause_all(filter),
+                (PauseTarget::Voices(filter), false) => runtime.resume_all(filter),
+            };
+            let action = if paused { "Pause" } else { "Resume" };
+            match result {
+                Ok(()) => println!("{action} requested"),
+                Err(err) => println!("{action} failed: {err}"),
+            }
+        }
+        Command::DevicePause(paused) => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            match runtime.runtime().set_device_paused(paused) {
+                Ok(()) => println!("Device paused: {paused}"),
+                Err(err) => println!("Device pause failed: {err}"),
+            }
+        }
         Command::Mode(mode) => {
             let Some(runtime) = ctx.runtime else {
                 println!("OpenAL runtime unavailable");
@@ -201,6 +314,25 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 print_status(runtime);
             }
         }
+        Command::HrtfList => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            print_hrtf_datasets(runtime);
+        }
+        Command::Hrtf(dataset) => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            let label = dataset.clone().unwrap_or_else(|| "default".to_string());
+            if runtime.runtime().set_hrtf_dataset(dataset).is_err() {
+                println!("Failed to set HRTF dataset");
+            } else {
+                println!("HRTF dataset set to {label} (used in hrtf mode)");
+            }
+        }
         Command::Distance(model) => {
             let Some(runtime) = ctx.runtime else {
                 println!("OpenAL runtime unavailable");
@@ -224,28 +356,76 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 println!("Muted: {muted}");
             }
         }
-        Command::Load { name, path } => {
+        Command::Volume(volume) => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            if runtime.runtime().set_master_volume(volume).is_err() {
+                println!("Failed to set volume");
+            } else {
+                println!("Volume: {volume:.3} ({:.1} dB)", db_from_gain(volume));
+            }
+        }
+        Command::BusVolume { bus, volume } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            if runtime.runtime().set_bus_volume(bus, volume).is_err() {
+                println!("Failed to set bus volume");
+            } else {
+                println!(
+                    "Bus {}: {volume:.3} ({:.1} dB)",
+                    bus.as_str(),
+                    db_from_gain(volume)
+                );
+            }
+        }
+        Command::TimeScale(scale) => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            if runtime.runtime().set_pitch_scale(scale).is_err() {
+                println!("Failed to set time scale");
+            } else {
+                println!("Time scale: {scale}");
+            }
+        }
+        Command::Load {
+            name,
+            path,
+            options,
+        } => {
             let Some(runtime) = ctx.runtime else {
                 println!("OpenAL runtime unavailable");
                 return false;
             };
             match std::fs::read(&path) {
-                Ok(bytes) => match decode_to_mono_i16(&bytes) {
-                    Ok(decoded) => {
-                        let key = ctx.registry.allocate_key();
-                        if runtime.runtime().create_buffer(key, decoded).is_ok() {
-                            let replaced = ctx.registry.insert(name.clone(), key);
+                Ok(bytes) => {
+                    let replaced = unregister(runtime, ctx.registry, &name);
+                    let key = match ctx.registry.register(name.as_str()) {
+                        Ok(key) => key,
+                        Err(err) => {
+                            println!("Failed to register {name}: {err}");
+                            return false;
+                        }
+                    };
+                    match runtime.runtime().decode_buffer_with(key, bytes, options) {
+                        Ok(()) => {
                             if let Some(old) = replaced {
-                                println!("Loaded {name} as {key} (replacing {old})");
+                                println!("Loading {name} as {key} (replacing {old})");
                             } else {
-                                println!("Loaded {name} as {key}");
+                                println!("Loading {name} as {key}");
                             }
-                        } else {
-                            println!("Failed to create buffer for {name}");
+                        }
+                        Err(err) => {
+                            ctx.registry.remove(&name);
+                            println!("Failed to queue decode for {name}: {err}");
                         }
                     }
-                    Err(err) => println!("Decode failed: {err}"),
-                },
+                }
                 Err(err) => {
                     if err.kind() == std::io::ErrorKind::NotFound {
                         println!("Read failed: cannot find file at {}", path.display());
@@ -255,6 +435,23 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 }
             }
         }
+        Command::Reload { name, path } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            let Some(key) = ctx.registry.get(&name) else {
+                println!("Unknown buffer: {name}");
+                return false;
+            };
+            match std::fs::read(&path) {
+                Ok(bytes) => match runtime.runtime().decode_replace_buffer(key, bytes) {
+                    Ok(()) => println!("Reloading {name} ({key})"),
+                    Err(err) => println!("Failed to queue decode for {name}: {err}"),
+                },
+                Err(err) => println!("Read failed for {}: {err}", path.display()),
+            }
+        }
         Command::Gen {
             name,
             kind,
@@ -272,16 +469,36 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 }
                 GenKind::Noise => sound::generate_noise(sound::DEFAULT_SAMPLE_RATE_HZ, seconds),
             };
-            let key = ctx.registry.allocate_key();
-            if runtime.runtime().create_buffer(key, decoded).is_ok() {
-                let replaced = ctx.registry.insert(name.clone(), key);
-                if let Some(old) = replaced {
-                    println!("Generated {name} as {key} (replacing {old})");
-                } else {
-                    println!("Generated {name} as {key}");
+            let replaced = unregister(runtime, ctx.registry, &name);
+            let key = match ctx.registry.register(name.as_str()) {
+                Ok(key) => key,
+                Err(err) => {
+                    println!("Failed to register {name}: {err}");
+                    return false;
+                }
+            };
+            match create_buffer_confirmed(runtime, key, decoded) {
+                Ok(()) => {
+                    if let Some(old) = replaced {
+                        println!("Generated {name} as {key} (replacing {old})");
+                    } else {
+                        println!("Generated {name} as {key}");
+                    }
+                }
+                Err(err) => {
+                    ctx.registry.remove(&name);
+                    println!("Failed to create generated buffer for {name}: {err}");
                 }
-            } else {
-                println!("Failed to create generated buffer for {name}");
+            }
+        }
+        Command::Unload { name } => {
+            let Some(runtime) = ctx.runtime else {
+                println!("OpenAL runtime unavailable");
+                return false;
+            };
+            match unregister(runtime, ctx.registry, &name) {
+                Some(key) => println!("Unloaded {name} ({key})"),
+                None => println!("Unknown buffer: {name}"),
             }
         }
         Command::Play {
@@ -302,6 +519,7 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 position,
                 gain,
                 pitch,
+                ..Default::default()
             };
             if runtime.runtime().play_one_shot(key, params).is_err() {
                 println!("Failed to play {name}");
@@ -330,6 +548,7 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
                 position,
                 gain,
                 pitch,
+                ..Default::default()
             };
             if runtime.runtime().start_loop(key, params).is_err() {
                 println!("Failed to start loop for {name}");
@@ -409,12 +628,29 @@ pub(crate) fn handle_command(command: Command, ctx: &mut CommandContext<'_, '_>)
 pub(crate) enum Command {
     Help,
     Status,
+    Voices,
     Mode(AudioRenderMode),
     Distance(DistanceModel),
+    HrtfList,
+    Hrtf(Option<String>),
     Mute(bool),
+    Volume(f32),
+    BusVolume {
+        bus: AudioBus,
+        volume: f32,
+    },
+    TimeScale(f32),
     Load {
         name: String,
         path: PathBuf,
+        options: ImportOptions,
+    },
+    Reload {
+        name: String,
+        path: PathBuf,
+    },
+    Unload {
+        name: String,
     },
     Gen {
         name: String,
@@ -437,6 +673,24 @@ pub(crate) enum Command {
     LoopStop {
         name: Option<String>,
     },
+    Seek {
+        voice: VoiceId,
+        seconds: f32,
+    },
+    Fade {
+        voice: VoiceId,
+        gain: f32,
+        seconds: f32,
+    },
+    Stop {
+        voice: VoiceId,
+        fade_seconds: f32,
+    },
+    Pause {
+        target: PauseTarget,
+        paused: bool,
+    },
+    DevicePause(bool),
     Listener {
         position: Vec3,
     },
@@ -454,6 +708,12 @@ pub(crate) enum Command {
     Quit,
 }
 
+#[derive(Clone, Copy)]
+pub(crate) enum PauseTarget {
+    Voice(VoiceId),
+    Voices(VoiceFilter),
+}
+
 pub(crate) enum GenKind {
     Sine,
     Noise,
@@ -511,15 +771,24 @@ fn normalize_input(input: &str) -> String {
     }
 }
 
+const MODE_USAGE: &str = "mode <auto|stereo|hrtf|surround|mono|uhj|quad|5.1|6.1|7.1|ambisonic>";
+
 fn parse_mode(args: &[String]) -> Result<Command, String> {
     let Some(value) = args.first() else {
-        return Err("mode <auto|stereo|hrtf|surround>".to_string());
+        return Err(MODE_USAGE.to_string());
     };
-    let mode = AudioRenderMode::parse(value)
-        .ok_or_else(|| "mode <auto|stereo|hrtf|surround>".to_string())?;
+    let mode = AudioRenderMode::parse(value).ok_or_else(|| MODE_USAGE.to_string())?;
     Ok(Command::Mode(mode))
 }
 
+fn parse_hrtf(args: &[String]) -> Command {
+    match args.join(" ").as_str() {
+        "" => Command::HrtfList,
+        "default" => Command::Hrtf(None),
+        name => Command::Hrtf(Some(name.to_string())),
+    }
+}
+
 fn parse_distance(args: &[String]) -> Result<Command, String> {
     let Some(value) = args.first() else {
         return Err(
@@ -534,6 +803,86 @@ fn parse_distance(args: &[String]) -> Result<Command, String> {
     Ok(Command::Distance(model))
 }
 
+fn parse_seek(args: &[String]) -> Result<Command, String> {
+    let [voice, seconds] = args else {
+        return Err("seek <voice> <seconds>".to_string());
+    };
+    let voice = parse_voice(voice)?;
+    let seconds = parse_f32(seconds)?;
+    Ok(Command::Seek { voice, seconds })
+}
+
+fn parse_fade(args: &[String]) -> Result<Command, String> {
+    let [voice, gain, seconds] = args else {
+        return Err("fade <voice> <gain> <seconds>".to_string());
+    };
+    Ok(Command::Fade {
+        voice: parse_voice(voice)?,
+        gain: parse_f32(gain)?,
+        seconds: parse_seconds(seconds)?,
+    })
+}
+
+fn parse_stop(args: &[String]) -> Result<Command, String> {
+    let (voice, fade_seconds) = match args {
+        [voice] => (voice, 0.0),
+        [voice, seconds] => (voice, parse_seconds(seconds)?),
+        _ => return Err("stop <voice> [fade_seconds]".to_string()),
+    };
+    Ok(Command::Stop {
+        voice: parse_voice(voice)?,
+        fade_seconds,
+    })
+}
+
+fn parse_voice(value: &str) -> Result<VoiceId, String> {
+    value
+        .trim_start_matches('#')
+        .parse::<VoiceId>()
+        .map_err(|_| format!("Invalid voice id: {value}"))
+}
+
+fn parse_seconds(value: &str) -> Result<f32, String> {
+    let seconds = parse_f32(value)?;
+    // Rejects negative and out-of-range values that `Duration::from_secs_f32` would panic on.
+    Duration::try_from_secs_f32(seconds).map_err(|_| format!("Invalid duration: {value}"))?;
+    Ok(seconds)
+}
+
+fn parse_pause(args: &[String], paused: bool) -> Result<Command, String> {
+    let usage = if paused {
+        "pause <voice|all|world>"
+    } else {
+        "resume <voice|all|world>"
+    };
+    let Some(value) = args.first() else {
+        return Err(usage.to_string());
+    };
+    let target = match value.as_str() {
+        "all" => PauseTarget::Voices(VoiceFilter::All),
+        "world" => PauseTarget::Voices(VoiceFilter::ExceptBus(AudioBus::Ui)),
+        voice => PauseTarget::Voice(
+            voice
+                .trim_start_matches('#')
+                .parse::<VoiceId>()
+                .map_err(|_| usage.to_string())?,
+        ),
+    };
+    Ok(Command::Pause { target, paused })
+}
+
+fn parse_device_pause(args: &[String]) -> Result<Command, String> {
+    let Some(value) = args.first() else {
+        return Err("device-pause <on|off>".to_string());
+    };
+    let paused = match value.as_str() {
+        "on" | "true" | "1" => true,
+        "off" | "false" | "0" => false,
+        _ => return Err("device-pause <on|off>".to_string()),
+    };
+    Ok(Command::DevicePause(paused))
+}
+
 fn parse_mute(args: &[String]) -> Result<Command, String> {
     let Some(value) = args.first() else {
         return Err("mute <on|off>".to_string());
@@ -546,13 +895,87 @@ fn parse_mute(args: &[String]) -> Result<Command, String> {
     Ok(Command::Mute(muted))
 }
 
+fn parse_volume(args: &[String]) -> Result<Command, String> {
+    let Some(value) = args.first() else {
+        return Err("volume <gain|<db>dB>".to_string());
+    };
+    Ok(Command::Volume(parse_gain(value)?))
+}
+
+fn parse_bus_volume(args: &[String]) -> Result<Command, String> {
+    const USAGE: &str = "bus <sfx|ui|music|vo|ambience> <gain|<db>dB>";
+    let [bus, value] = args else {
+        return Err(USAGE.to_string());
+    };
+    let bus = AudioBus::parse(bus).ok_or_else(|| USAGE.to_string())?;
+    let volume = parse_gain(value)?;
+    Ok(Command::BusVolume { bus, volume })
+}
+
+/// A linear gain, or decibels with a `dB` suffix.
+fn parse_gain(value: &str) -> Result<f32, String> {
+    let volume = match value.to_ascii_lowercase().strip_suffix("db") {
+        Some(db) => gain_from_db(parse_f32(db)?),
+        None => parse_f32(value)?,
+    };
+    if volume < 0.0 {
+        return Err("volume must be >= 0".to_string());
+    }
+    Ok(volume)
+}
+
+fn parse_timescale(args: &[String]) -> Result<Command, String> {
+    let Some(value) = args.first() else {
+        return Err("timescale <scale>".to_string());
+    };
+    let scale = parse_f32(value)?;
+    if scale <= 0.0 {
+        return Err("timescale must be > 0".to_string());
+    }
+    Ok(Command::TimeScale(scale))
+}
+
 fn parse_load(args: &[String]) -> Result<Command, String> {
+    const USAGE: &str = "load <name> <path> [normalize] [trim] [gain <db>] [loop <start> <end>]";
     if args.len() < 2 {
-        return Err("load <name> <path>".to_string());
+        return Err(USAGE.to_string());
+    }
+    let mut options = ImportOptions::default();
+    let mut rest = args[2..].iter();
+    while let Some(flag) = rest.next() {
+        let mut value = || rest.next().ok_or_else(|| USAGE.to_string());
+        match flag.as_str() {
+            "normalize" => options.normalize = true,
+            "trim" => options.trim_silence = true,
+            "gain" => options.gain_db = parse_f32(value()?)?,
+            "loop" => {
+                options.loop_start = Some(parse_f32(value()?)?);
+                options.loop_end = Some(parse_f32(value()?)?);
+            }
+            _ => return Err(USAGE.to_string()),
+        }
     }
     Ok(Command::Load {
         name: args[0].clone(),
         path: PathBuf::from(&args[1]),
+        options,
+    })
+}
+
+fn parse_unload(args: &[String]) -> Result<Command, String> {
+    let [name] = args else {
+        return Err("unload <name>".to_string());
+    };
+    Ok(Command::Unload { name: name.clone() })
+}
+
+fn parse_reload(args: &[String]) -> Result<Command, String> {
+    let [name, path] = args else {
+        return Err("reload <name> <path>".to_string());
+    };
+    Ok(Command::Reload {
+        name: name.clone(),
+        path: PathBuf::from(path),
     })
 }
 
@@ -719,22 +1142,124 @@ fn parse_f32(value: &str) -> Result<f32, String> {
     Ok(parsed)
 }
 
+/// Forgets `name` and frees its buffer, returning the old key.
+fn unregister(
+    runtime: &OpenalRuntime,
+    registry: &mut SoundRegistry,
+    name: &str,
+) -> Option<BufferKey> {
+    let key = registry.remove(name)?;
+    if let Err(err) = runtime.runtime().delete_buffer(key) {
+        println!("Failed to delete buffer {key}: {err}");
+    }
+    Some(key)
+}
+
+/// Uploads a buffer and waits briefly for the audio thread to confirm it, so the REPL reports the
+/// real outcome instead of "queued". A command still pending after the timeout is treated as
+/// accepted; any later failure shows up in `status`.
+fn create_buffer_confirmed(
+    runtime: &OpenalRuntime,
+    key: BufferKey,
+    decoded: DecodedAudioMono16,
+) -> Result<(), RuntimeError> {
+    let ticket = runtime.runtime().create_buffer_acked(key, decoded)?;
+    ticket.wait_timeout(BUFFER_ACK_TIMEOUT).unwrap_or(Ok(()))
+}
+
 fn print_status(runtime: &OpenalRuntime) {
     let status = runtime.runtime().status();
     let output_mode = status.output_mode.as_deref().unwrap_or("unknown");
-    let last_error = status.last_error.as_deref().unwrap_or("none");
+    let last_error = status
+        .last_error()
+        .map_or_else(|| "none".to_string(), |record| record.error.to_string());
 
     println!("render_mode: {}", status.render_mode.as_str());
+    if let Some(requested) = &status.output_mode_requested {
+        println!("output_mode_requested: {requested}");
+    }
     match status.output_mode_raw {
         Some(raw) => println!("output_mode: {output_mode} (raw=0x{raw:04X})"),
         None => println!("output_mode: {output_mode}"),
     }
+    match status.device_sample_rate_hz {
+        Some(rate) => println!("sample_rate: {rate} Hz"),
+        None => println!("sample_rate: unknown"),
+    }
     println!("distance_model: {}", status.distance_model.as_str());
     println!("hrtf_active: {}", status.hrtf_active);
+    if let Some(dataset) = &status.hrtf_dataset {
+        println!("hrtf_dataset: {dataset}");
+    }
     println!("muted: {}", status.muted);
+    println!(
+        "master_volume: {:.3} ({:.1} dB)",
+        status.master_volume,
+        db_from_gain(status.master_volume)
+    );
+    let buses = AudioBus::ALL
+        .iter()
+        .map(|bus| format!("{}={:.3}", bus.as_str(), status.bus_volumes.get(*bus)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!("bus_volumes: {buses}");
+    println!("pitch_scale: {}", status.pitch_scale);
+    println!("device_paused: {}", status.device_paused);
     println!("buffers: {}", status.loaded_buffers);
-    println!("sources: {}", status.active_sources);
-    println!("last_error: {last_error}");
+    println!(
+        "sources: {} / {}",
+        status.active_sources, status.source_pool_size
+    );
+    println!(
+        "commands: queued={} coalesced={} dropped={}",
+        status.command_queue_depth, status.commands_coalesced, status.commands_dropped
+    );
+    println!("events_dropped: {}", status.events_dropped);
+    println!(
+        "decodes: pending={} completed={} failed={}",
+        status.decodes_pending, status.decodes_completed, status.decodes_failed
+    );
+    println!(
+        "last_error: {last_error} ({} recent)",
+        status.recent_errors.len()
+    );
+}
+
+fn print_hrtf_datasets(runtime: &OpenalRuntime) {
+    let status = runtime.runtime().status();
+    if status.hrtf_datasets.is_empty() {
+        println!("No HRTF datasets reported by the device");
+        return;
+    }
+    for (index, name) in status.hrtf_datasets.iter().enumerate() {
+        let active = status.hrtf_dataset.as_ref() == Some(name);
+        println!("{}{index}: {name}", if active { "* " } else { "  " });
+    }
+}
+
+fn print_voices(runtime: &OpenalRuntime) {
+    let snapshot = runtime.runtime().voice_snapshot();
+    if snapshot.voices.is_empty() {
+        println!("no active voices");
+        return;
+    }
+    for voice in &snapshot.voices {
+        println!(
+            "#{} buffer={} bus={} {}{} pos={:.2}/{:.2}s dist={:.2} atten={:.3} gain={:.2} prio={} age={:.2}s",
+            voice.voice,
+            voice.key,
+            voice.bus.as_str(),
+            voice.state.as_str(),
+            if voice.looping { " (loop)" } else { "" },
+            voice.offset.seconds,
+            voice.offset.length_seconds,
+            voice.distance,
+            voice.attenuation,
+            voice.gain,
+            voice.priority,
+            voice.age.as_secs_f32(),
+        );
+    }
 }
 
 fn print_prompt_once() {
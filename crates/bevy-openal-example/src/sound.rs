@@ -1,14 +1,15 @@
 use crate::cli;
 use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::message::MessageReader;
 use bevy_ecs::prelude::{Commands, Query, Res, ResMut, Resource, With};
 use bevy_math::Vec3;
 use bevy_openal::{
-    BufferKey, DecodedAudioMono16, OpenalListener, OpenalRuntime, PlayOneShotParams,
+    AudioEvent, BufferKey, DecodedAudioMono16, OpenalAudioEvent, OpenalListener, OpenalRuntime,
+    PlayOneShotParams, SoundRegistry,
 };
 use bevy_time::{Time, Timer, TimerMode};
 use bevy_transform::components::GlobalTransform;
 use bevy_transform::prelude::Transform;
-use std::collections::HashMap;
 
 pub(crate) const DEFAULT_SAMPLE_RATE_HZ: u32 = 48_000;
 const DEFAULT_BEEP_NAME: &str = "beep";
@@ -16,40 +17,6 @@ const DEFAULT_BEEP_SECONDS: f32 = 0.25;
 pub(crate) const DEFAULT_BEEP_FREQ_HZ: f32 = 880.0;
 const ORBIT_FIRE_INTERVAL_SECS: f32 = 0.2;
 
-#[derive(Resource)]
-pub(crate) struct BufferRegistry {
-    next_key: BufferKey,
-    name_to_key: HashMap<String, BufferKey>,
-}
-
-impl Default for BufferRegistry {
-    fn default() -> Self {
-        Self {
-            next_key: 1,
-            name_to_key: HashMap::new(),
-        }
-    }
-}
-
-impl BufferRegistry {
-    pub(crate) fn allocate_key(&mut self) -> BufferKey {
-        let key = self.next_key;
-        self.next_key = self.next_key.wrapping_add(1);
-        if self.next_key == 0 {
-            self.next_key = 1;
-        }
-        key
-    }
-
-    pub(crate) fn insert(&mut self, name: String, key: BufferKey) -> Option<BufferKey> {
-        self.name_to_key.insert(name, key)
-    }
-
-    pub(crate) fn get(&self, name: &str) -> Option<BufferKey> {
-        self.name_to_key.get(name).copied()
-    }
-}
-
 #[derive(Default, Resource)]
 pub(crate) struct ListenerTarget {
     position: Vec3,
@@ -147,7 +114,7 @@ impl OrbitState {
 
 pub(crate) fn setup_listener(mut commands: Commands) {
     commands.spawn((
-        OpenalListener,
+        OpenalListener::default(),
         Transform::default(),
         GlobalTransform::default(),
     ));
@@ -155,7 +122,7 @@ pub(crate) fn setup_listener(mut commands: Commands) {
 
 pub(crate) fn ensure_default_sample(
     runtime: Option<Res<OpenalRuntime>>,
-    mut registry: ResMut<BufferRegistry>,
+    mut registry: ResMut<SoundRegistry>,
     mut state: ResMut<DefaultSampleState>,
 ) {
     if state.loaded {
@@ -170,14 +137,18 @@ pub(crate) fn ensure_default_sample(
         DEFAULT_BEEP_SECONDS,
         DEFAULT_BEEP_FREQ_HZ,
     );
-    let key = registry.allocate_key();
+    let Ok(key) = registry.register(DEFAULT_BEEP_NAME) else {
+        println!("Default sample name {DEFAULT_BEEP_NAME} is already registered");
+        state.loaded = true;
+        return;
+    };
     if runtime.runtime().create_buffer(key, decoded).is_ok() {
-        registry.insert(DEFAULT_BEEP_NAME.to_string(), key);
         state.loaded = true;
         println!("Loaded default sample: {DEFAULT_BEEP_NAME}");
         cli::print_help_hint();
         cli::print_prompt();
     } else {
+        registry.remove(DEFAULT_BEEP_NAME);
         println!("Failed to create default sample buffer");
         cli::print_prompt();
     }
@@ -196,6 +167,31 @@ pub(crate) fn apply_listener_target(
     transform.translation = target.position;
 }
 
+pub(crate) fn report_audio_events(mut events: MessageReader<OpenalAudioEvent>) {
+    for OpenalAudioEvent(event) in events.read() {
+        match event {
+            AudioEvent::PlayFailed { voice, key, reason } => {
+                println!("Voice {voice} (buffer {key}) failed to play: {reason}");
+            }
+            AudioEvent::DeviceLost => {
+                println!("Audio device lost; switch render mode to reopen it");
+            }
+            AudioEvent::VoicePaused { voice, key } => {
+                println!("Voice {voice} (buffer {key}) paused");
+            }
+            AudioEvent::VoiceResumed { voice, key } => {
+                println!("Voice {voice} (buffer {key}) resumed");
+            }
+            AudioEvent::BufferReady { key } => println!("Buffer {key} ready"),
+            AudioEvent::BufferFailed { key, reason } => {
+                println!("Buffer {key} failed to load: {reason}");
+            }
+            _ => continue,
+        }
+        cli::print_prompt();
+    }
+}
+
 pub(crate) fn update_orbit(
     time: Res<Time>,
     runtime: Option<Res<OpenalRuntime>>,
@@ -228,6 +224,7 @@ pub(crate) fn update_orbit(
             position,
             gain: 1.0,
             pitch: 1.0,
+            ..Default::default()
         };
         let _ = runtime.runtime().play_one_shot(orbit.buffer_key, params);
     }
@@ -245,6 +242,7 @@ pub(crate) fn generate_sine(sample_rate_hz: u32, seconds: f32, freq_hz: f32) ->
     DecodedAudioMono16 {
         sample_rate_hz,
         samples,
+        loop_frames: None,
     }
 }
 
@@ -259,6 +257,7 @@ pub(crate) fn generate_noise(sample_rate_hz: u32, seconds: f32) -> DecodedAudioM
     DecodedAudioMono16 {
         sample_rate_hz,
         samples,
+        loop_frames: None,
     }
 }
 
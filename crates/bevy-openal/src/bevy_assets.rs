@@ -1,44 +1,535 @@
-use bevy_app::{App, Plugin};
-use bevy_asset::{io::Reader, Asset, AssetApp, AssetLoader, LoadContext};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetEvent, AssetLoader, Assets, LoadContext, UntypedAssetId,
+};
+use bevy_ecs::message::MessageReader;
+use bevy_ecs::prelude::*;
 use bevy_reflect::TypePath;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+    AudioDecoder, AudioEvent, BufferKey, DecodeError, DecodedAudio, DecodedAudioInfo,
+    DecodedAudioMono16, ImportOptions, OpenalAudioEvent, OpenalRuntime, SymphoniaDecoder,
+};
+
+/// First [`BufferKey`] handed out for audio assets. Keys from here up are reserved for
+/// [`OpenalAudioBuffers`]; keys chosen by hand should stay below it.
+pub const ASSET_BUFFER_KEY_BASE: BufferKey = 0x8000_0000;
 
 #[derive(Asset, TypePath, Debug, Clone)]
 pub struct OpenalAudioBytes {
     pub bytes: Vec<u8>,
+    /// Conditioning applied when the bytes are decoded into a buffer.
+    pub import: ImportOptions,
+}
+
+/// Mono PCM decoded by [`OpenalDecodedAudioLoader`], or generated in code and added with
+/// `Assets::add`.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct OpenalDecodedAudio {
+    pub pcm: DecodedAudioMono16,
+    pub info: DecodedAudioInfo,
+    /// Conditioning applied when the PCM is turned into a buffer.
+    pub import: ImportOptions,
+}
+
+impl From<DecodedAudio> for OpenalDecodedAudio {
+    fn from(decoded: DecodedAudio) -> Self {
+        Self {
+            pcm: decoded.pcm,
+            info: decoded.info,
+            import: ImportOptions::default(),
+        }
+    }
+}
+
+impl From<DecodedAudioMono16> for OpenalDecodedAudio {
+    /// Wraps generated PCM, describing it as single-channel `pcm`.
+    fn from(pcm: DecodedAudioMono16) -> Self {
+        let duration = if pcm.sample_rate_hz == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(pcm.samples.len() as f64 / f64::from(pcm.sample_rate_hz))
+        };
+        let info = DecodedAudioInfo {
+            codec: "pcm".to_string(),
+            channels: 1,
+            sample_rate_hz: pcm.sample_rate_hz,
+            duration,
+        };
+        Self {
+            pcm,
+            info,
+            import: ImportOptions::default(),
+        }
+    }
 }
 
+/// Extensions of the codecs compiled into this build.
+const EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "wav")]
+    "wav",
+    #[cfg(any(feature = "vorbis", feature = "opus"))]
+    "ogg",
+    #[cfg(feature = "opus")]
+    "opus",
+    #[cfg(feature = "flac")]
+    "flac",
+    #[cfg(feature = "mp3")]
+    "mp3",
+];
+
+/// Loads audio files as [`OpenalAudioBytes`].
+///
+/// The loader settings are [`ImportOptions`], so each asset's `.meta` file can set its import rate,
+/// normalization, gain, silence trimming and loop region.
 #[derive(TypePath)]
 pub struct OpenalAudioBytesLoader;
 
 impl AssetLoader for OpenalAudioBytesLoader {
     type Asset = OpenalAudioBytes;
-    type Settings = ();
+    type Settings = ImportOptions;
     type Error = std::io::Error;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(OpenalAudioBytes {
+            bytes,
+            import: *settings,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        EXTENSIONS
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum OpenalDecodedAudioLoaderError {
+    #[error("audio read failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+/// Decodes audio files into [`OpenalDecodedAudio`] with [`SymphoniaDecoder`], on Bevy's asset task
+/// pool rather than the runtime's decode pool.
+///
+/// Registered for the same extensions as [`OpenalAudioBytesLoader`]; the asset type requested from
+/// the `AssetServer` picks the loader. Settings are [`ImportOptions`], as for raw bytes.
+#[derive(TypePath)]
+pub struct OpenalDecodedAudioLoader;
+
+impl AssetLoader for OpenalDecodedAudioLoader {
+    type Asset = OpenalDecodedAudio;
+    type Settings = ImportOptions;
+    type Error = OpenalDecodedAudioLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
         _load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        Ok(OpenalAudioBytes { bytes })
+        let decoded = SymphoniaDecoder.decode(&bytes)?;
+        Ok(OpenalDecodedAudio {
+            import: *settings,
+            ..decoded.into()
+        })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["wav", "ogg", "flac", "mp3"]
+        EXTENSIONS
+    }
+}
+
+/// Where a loaded [`OpenalAudioBytes`] asset is on its way to becoming a runtime buffer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AudioBufferState {
+    /// Loaded, waiting for the OpenAL runtime to accept the decode job.
+    Queued,
+    Decoding,
+    Ready,
+    /// The asset changed on disk; the previous buffer keeps playing until the new one replaces it.
+    Reloading,
+    Failed,
+}
+
+impl AudioBufferState {
+    /// Whether the buffer exists and can be played.
+    pub fn is_playable(self) -> bool {
+        matches!(self, AudioBufferState::Ready | AudioBufferState::Reloading)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct AudioBufferEntry {
+    key: BufferKey,
+    state: AudioBufferState,
+    /// A decode job still has to be handed to the runtime.
+    submit: bool,
+    /// The asset changed again while a decode was in flight; decode once more when it lands.
+    stale: bool,
+}
+
+impl AudioBufferEntry {
+    /// Schedules a decode of the asset's current bytes, replacing the buffer if it exists.
+    fn reload(&mut self) {
+        if !self.submit
+            && matches!(
+                self.state,
+                AudioBufferState::Decoding | AudioBufferState::Reloading
+            )
+        {
+            self.stale = true;
+            return;
+        }
+        self.submit = true;
+        if self.state.is_playable() {
+            self.state = AudioBufferState::Reloading;
+        } else {
+            self.state = AudioBufferState::Queued;
+        }
+    }
+
+    /// Applies the outcome of the in-flight decode.
+    fn finish(&mut self, succeeded: bool) {
+        self.state = match (succeeded, self.state) {
+            (true, _) => AudioBufferState::Ready,
+            // A failed reload leaves the previous buffer in place.
+            (false, AudioBufferState::Reloading) => AudioBufferState::Ready,
+            (false, _) => AudioBufferState::Failed,
+        };
+        if std::mem::take(&mut self.stale) {
+            self.reload();
+        }
+    }
+}
+
+/// Maps audio assets to the runtime buffers decoded from them.
+///
+/// Every `OpenalAudioBytes` or `OpenalDecodedAudio` asset that finishes loading gets a
+/// [`BufferKey`] (from [`ASSET_BUFFER_KEY_BASE`] up) and is decoded (or, for decoded audio, just
+/// conditioned) on the runtime's background decode pool. When the asset is modified (hot reload),
+/// it is imported again and swapped into the same buffer key; voices using it keep playing with
+/// the new audio. When the asset is removed, its buffer is deleted and the key reused.
+#[derive(Resource, Debug)]
+pub struct OpenalAudioBuffers {
+    next_key: BufferKey,
+    entries: HashMap<UntypedAssetId, AudioBufferEntry>,
+    /// Keys of removed assets, free for reuse.
+    free_keys: Vec<BufferKey>,
+    /// Removed assets whose decode was still in flight, with whether an older buffer exists.
+    /// Deleted and freed once the decode lands.
+    retiring: HashMap<BufferKey, bool>,
+}
+
+impl Default for OpenalAudioBuffers {
+    fn default() -> Self {
+        Self {
+            next_key: ASSET_BUFFER_KEY_BASE,
+            entries: HashMap::new(),
+            free_keys: Vec::new(),
+            retiring: HashMap::new(),
+        }
     }
 }
 
-/// Registers `OpenalAudioBytes` as a Bevy asset and installs a raw-bytes loader for common
-/// audio file extensions. This is intended to support OpenAL-based playback without enabling
-/// Bevy's built-in audio output plugin.
+impl OpenalAudioBuffers {
+    /// Buffer key assigned to the asset, whether or not its buffer is ready yet.
+    pub fn key(&self, id: impl Into<UntypedAssetId>) -> Option<BufferKey> {
+        self.entries.get(&id.into()).map(|entry| entry.key)
+    }
+
+    pub fn state(&self, id: impl Into<UntypedAssetId>) -> Option<AudioBufferState> {
+        self.entries.get(&id.into()).map(|entry| entry.state)
+    }
+
+    /// Buffer key of the asset once its buffer can be played.
+    pub fn ready_key(&self, id: impl Into<UntypedAssetId>) -> Option<BufferKey> {
+        self.entries
+            .get(&id.into())
+            .filter(|entry| entry.state.is_playable())
+            .map(|entry| entry.key)
+    }
+
+    fn insert(&mut self, id: UntypedAssetId) -> BufferKey {
+        if let Some(entry) = self.entries.get(&id) {
+            return entry.key;
+        }
+        let key = self.free_keys.pop().unwrap_or_else(|| {
+            let key = self.next_key;
+            self.next_key = self
+                .next_key
+                .checked_add(1)
+                .unwrap_or(ASSET_BUFFER_KEY_BASE);
+            key
+        });
+        self.entries.insert(
+            id,
+            AudioBufferEntry {
+                key,
+                state: AudioBufferState::Queued,
+                submit: true,
+                stale: false,
+            },
+        );
+        key
+    }
+
+    /// Forgets a removed asset. Returns the key whose buffer should be deleted now, if any; a
+    /// buffer still being decoded is deleted by [`Self::finish_retiring`] once it lands.
+    fn remove(&mut self, id: UntypedAssetId) -> Option<BufferKey> {
+        let entry = self.entries.remove(&id)?;
+        let in_flight = !entry.submit
+            && matches!(
+                entry.state,
+                AudioBufferState::Decoding | AudioBufferState::Reloading
+            );
+        if in_flight {
+            self.retiring
+                .insert(entry.key, entry.state == AudioBufferState::Reloading);
+            return None;
+        }
+        self.free_keys.push(entry.key);
+        entry.state.is_playable().then_some(entry.key)
+    }
+
+    /// Frees a retiring key once its decode finished. Returns the key if a buffer now exists.
+    fn finish_retiring(&mut self, key: BufferKey, succeeded: bool) -> Option<BufferKey> {
+        let had_buffer = self.retiring.remove(&key)?;
+        self.free_keys.push(key);
+        (succeeded || had_buffer).then_some(key)
+    }
+
+    fn entry_mut(&mut self, key: BufferKey) -> Option<&mut AudioBufferEntry> {
+        self.entries.values_mut().find(|entry| entry.key == key)
+    }
+}
+
+/// Registers `OpenalAudioBytes` and `OpenalDecodedAudio` as Bevy assets and installs their loaders
+/// for common audio file extensions. This is intended to support OpenAL-based playback without
+/// enabling Bevy's built-in audio output plugin.
+///
+/// Loaded assets are turned into runtime buffers automatically (see [`OpenalAudioBuffers`]), so
+/// `OpenalPlayOneShot` and `OpenalEmitter` can refer to either handle type directly.
 pub struct BevyOpenalAssetsPlugin;
 
 impl Plugin for BevyOpenalAssetsPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<OpenalAudioBytes>()
-            .register_asset_loader(OpenalAudioBytesLoader);
+            .init_asset::<OpenalDecodedAudio>()
+            .register_asset_loader(OpenalAudioBytesLoader)
+            .register_asset_loader(OpenalDecodedAudioLoader)
+            .init_resource::<OpenalAudioBuffers>()
+            .add_message::<OpenalAudioEvent>()
+            .add_systems(
+                Update,
+                (
+                    register_audio_assets_system,
+                    submit_audio_decodes_system,
+                    track_audio_buffers_system,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Copy, Clone)]
+enum AssetChange {
+    Added,
+    Modified,
+    Removed,
+}
+
+fn asset_change<A: Asset>(event: &AssetEvent<A>) -> Option<(UntypedAssetId, AssetChange)> {
+    match event {
+        AssetEvent::Added { id } => Some((id.untyped(), AssetChange::Added)),
+        AssetEvent::Modified { id } => Some((id.untyped(), AssetChange::Modified)),
+        AssetEvent::Removed { id } => Some((id.untyped(), AssetChange::Removed)),
+        _ => None,
+    }
+}
+
+fn register_audio_assets_system(
+    runtime: Option<Res<OpenalRuntime>>,
+    mut bytes_events: MessageReader<AssetEvent<OpenalAudioBytes>>,
+    mut decoded_events: MessageReader<AssetEvent<OpenalDecodedAudio>>,
+    mut buffers: ResMut<OpenalAudioBuffers>,
+) {
+    let bytes_events = bytes_events.read().filter_map(asset_change);
+    let decoded_events = decoded_events.read().filter_map(asset_change);
+    for (id, change) in bytes_events.chain(decoded_events) {
+        match (change, buffers.entries.get_mut(&id)) {
+            (AssetChange::Removed, _) => {
+                if let Some(key) = buffers.remove(id) {
+                    delete_asset_buffer(runtime.as_deref(), key);
+                }
+            }
+            (AssetChange::Modified, Some(entry)) => entry.reload(),
+            _ => {
+                buffers.insert(id);
+            }
+        }
+    }
+}
+
+fn delete_asset_buffer(runtime: Option<&OpenalRuntime>, key: BufferKey) {
+    let Some(runtime) = runtime else {
+        return;
+    };
+    if let Err(err) = runtime.runtime().delete_buffer(key) {
+        warn!(key, error = %err, "Failed to delete removed audio asset buffer");
+    }
+}
+
+/// Hands queued assets to the runtime's decode pool once the runtime exists.
+fn submit_audio_decodes_system(
+    runtime: Option<Res<OpenalRuntime>>,
+    bytes_assets: Res<Assets<OpenalAudioBytes>>,
+    decoded_assets: Res<Assets<OpenalDecodedAudio>>,
+    mut buffers: ResMut<OpenalAudioBuffers>,
+) {
+    let Some(runtime) = runtime else {
+        return;
+    };
+    if runtime.runtime().is_shutdown_requested() {
+        return;
+    }
+    for (id, entry) in buffers.entries.iter_mut() {
+        if !entry.submit {
+            continue;
+        }
+        entry.submit = false;
+        let runtime = runtime.runtime();
+        let replace = entry.state == AudioBufferState::Reloading;
+        let bytes = id
+            .try_typed::<OpenalAudioBytes>()
+            .ok()
+            .and_then(|id| bytes_assets.get(id));
+        let decoded = id
+            .try_typed::<OpenalDecodedAudio>()
+            .ok()
+            .and_then(|id| decoded_assets.get(id));
+        let result = match (bytes, decoded) {
+            (Some(asset), _) if replace => {
+                runtime.decode_replace_buffer_with(entry.key, asset.bytes.clone(), asset.import)
+            }
+            (Some(asset), _) => {
+                runtime.decode_buffer_with(entry.key, asset.bytes.clone(), asset.import)
+            }
+            (_, Some(asset)) if replace => {
+                runtime.import_replace_buffer(entry.key, asset.pcm.clone(), asset.import)
+            }
+            (_, Some(asset)) => runtime.import_buffer(entry.key, asset.pcm.clone(), asset.import),
+            // Loaded and dropped again before the runtime came up; nothing to decode.
+            (None, None) => {
+                entry.finish(false);
+                continue;
+            }
+        };
+        match result {
+            Ok(()) if entry.state == AudioBufferState::Queued => {
+                entry.state = AudioBufferState::Decoding;
+            }
+            Ok(()) => {}
+            Err(err) => {
+                warn!(key = entry.key, error = %err, "Failed to queue audio asset decode");
+                entry.finish(false);
+            }
+        }
+    }
+}
+
+fn track_audio_buffers_system(
+    runtime: Option<Res<OpenalRuntime>>,
+    mut events: MessageReader<OpenalAudioEvent>,
+    mut buffers: ResMut<OpenalAudioBuffers>,
+) {
+    for OpenalAudioEvent(event) in events.read() {
+        let (key, succeeded) = match event {
+            AudioEvent::BufferReady { key } => (*key, true),
+            AudioEvent::BufferFailed { key, .. } => (*key, false),
+            _ => continue,
+        };
+        if let Some(entry) = buffers.entry_mut(key) {
+            entry.finish(succeeded);
+        } else if let Some(key) = buffers.finish_retiring(key, succeeded) {
+            delete_asset_buffer(runtime.as_deref(), key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AssetEvent, AssetId};
+    use bevy_ecs::message::Messages;
+    use bevy_ecs::prelude::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    use super::{
+        register_audio_assets_system, AudioBufferEntry, AudioBufferState, OpenalAudioBuffers,
+        OpenalAudioBytes, OpenalDecodedAudio, ASSET_BUFFER_KEY_BASE,
+    };
+
+    #[test]
+    fn removed_assets_drop_their_entry_and_free_the_key() {
+        let mut world = World::new();
+        world.init_resource::<OpenalAudioBuffers>();
+        world.init_resource::<Messages<AssetEvent<OpenalAudioBytes>>>();
+        world.init_resource::<Messages<AssetEvent<OpenalDecodedAudio>>>();
+        let id = AssetId::<OpenalAudioBytes>::default();
+
+        world.write_message(AssetEvent::Added { id });
+        world.run_system_once(register_audio_assets_system).unwrap();
+        assert_eq!(
+            world.resource::<OpenalAudioBuffers>().key(id),
+            Some(ASSET_BUFFER_KEY_BASE)
+        );
+
+        world.write_message(AssetEvent::Removed { id });
+        world.run_system_once(register_audio_assets_system).unwrap();
+        let buffers = world.resource::<OpenalAudioBuffers>();
+        assert_eq!(buffers.key(id), None);
+        assert_eq!(buffers.free_keys, vec![ASSET_BUFFER_KEY_BASE]);
+    }
+
+    #[test]
+    fn reload_keeps_old_buffer_playable_and_coalesces_changes() {
+        let mut entry = AudioBufferEntry {
+            key: 1,
+            state: AudioBufferState::Ready,
+            submit: false,
+            stale: false,
+        };
+        entry.reload();
+        assert_eq!(entry.state, AudioBufferState::Reloading);
+        assert!(entry.submit);
+
+        // Submitted, then changed twice more while decoding: one more decode afterwards.
+        entry.submit = false;
+        entry.reload();
+        entry.reload();
+        assert!(!entry.submit);
+        entry.finish(false);
+        assert_eq!(entry.state, AudioBufferState::Reloading);
+        assert!(entry.submit && !entry.stale);
+
+        entry.submit = false;
+        entry.finish(true);
+        assert_eq!(entry.state, AudioBufferState::Ready);
+        assert!(!entry.submit);
     }
 }
@@ -1,14 +1,29 @@
 use bevy_app::{App, Plugin, Startup, Update};
-use bevy_ecs::message::MessageReader;
+#[cfg(feature = "bevy-assets")]
+use bevy_asset::{Handle, UntypedAssetId};
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::message::{MessageReader, MessageWriter};
 use bevy_ecs::prelude::*;
-use bevy_math::Vec3;
-use bevy_transform::components::GlobalTransform;
+use bevy_ecs::system::SystemParam;
+use bevy_ecs::world::DeferredWorld;
+use bevy_math::{Quat, Vec3};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{error, warn};
 
+#[cfg(feature = "config")]
+use crate::config::{load_settings_file_system, save_settings_file_system, SavedSettings};
 use crate::{
-    AudioRenderMode, AudioRuntime, AudioRuntimeConfig, BufferKey, DistanceModel, ListenerFrame,
-    PlayOneShotParams, RuntimeError,
+    db_from_gain, gain_from_db, AudioBus, AudioCommand, AudioEvent, AudioRenderMode, AudioRuntime,
+    AudioRuntimeConfig, AudioTicket, AudioVoiceSnapshot, BufferKey, BusVolumes, DistanceModel,
+    ListenerFrame, PlayOneShotParams, RuntimeError, SoundRegistry, VoiceId,
 };
+#[cfg(feature = "bevy-assets")]
+use crate::{AudioBufferState, OpenalAudioBuffers, OpenalAudioBytes, OpenalDecodedAudio};
 
 pub struct BevyOpenalPlugin;
 
@@ -16,7 +31,13 @@ impl Plugin for BevyOpenalPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<OpenalSettings>()
             .init_resource::<OpenalStatus>()
+            .init_resource::<SoundRegistry>()
+            .init_resource::<EmitterVoices>()
             .add_message::<OpenalPlayOneShot>()
+            .add_message::<OpenalEmitterControl>()
+            .add_message::<OpenalAudioEvent>()
+            .add_message::<OpenalSoundFailed>()
+            .add_message::<OpenalSettingsEvent>()
             .add_systems(Startup, init_openal_runtime)
             .add_systems(
                 Update,
@@ -25,36 +46,107 @@ impl Plugin for BevyOpenalPlugin {
                     sync_status_system,
                     sync_listener_system,
                     play_one_shot_system,
+                    (
+                        stop_removed_emitters_system,
+                        control_emitters_system,
+                        start_emitters_system,
+                        sync_emitters_system,
+                    )
+                        .chain(),
+                    (publish_events_system, release_finished_emitters_system).chain(),
                 ),
             );
+
+        #[cfg(feature = "config")]
+        app.init_resource::<SavedSettings>()
+            .add_systems(
+                Startup,
+                load_settings_file_system.before(init_openal_runtime),
+            )
+            .add_systems(Update, save_settings_file_system);
     }
 }
 
-#[derive(Resource, Clone)]
+/// Startup and live settings for [`BevyOpenalPlugin`].
+///
+/// With the `config` feature these can be loaded from and saved to a RON file; see
+/// `OpenalSettingsFile`.
+#[derive(Resource, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct OpenalSettings {
     pub render_mode: AudioRenderMode,
+    /// HRTF dataset used by [`AudioRenderMode::HeadphonesHrtf`], by name as listed in
+    /// `AudioRuntimeStatus::hrtf_datasets`. `None` or an unknown name uses the default.
+    pub hrtf_dataset: Option<String>,
     pub distance_model: DistanceModel,
+    /// Size of the source pool. Lowering it steals the lowest-priority one-shots. Raising it past
+    /// what the open context allows reopens the device, which cuts every playing one-shot with
+    /// [`AudioEvent::VoiceDropped`]; the loop, buffers and looping [`OpenalEmitter`]s are
+    /// restored.
     pub max_sources: usize,
     pub preferred_device: Option<String>,
+    /// Silences the output; [`Self::master_volume`] is kept and restored on unmute.
     pub muted: bool,
+    /// Listener gain (linear, 1.0 = unchanged). See [`Self::set_master_volume_db`].
+    pub master_volume: f32,
+    /// Pitch and playback-speed multiplier for every voice outside [`AudioBus::Ui`], for slow
+    /// motion and similar time-scale effects.
+    pub pitch_scale: f32,
+    /// Linear gain per [`AudioBus`], on top of [`Self::master_volume`].
+    pub bus_volumes: BusVolumes,
+    /// Fraction of the previous velocity kept each frame when deriving listener and emitter
+    /// velocity from transform changes (0 = raw per-frame velocity, towards 1 = smoother).
+    pub velocity_smoothing: f32,
+    /// A per-frame move longer than this is treated as a teleport and resets velocity to zero
+    /// instead of producing a doppler spike.
+    pub teleport_distance: f32,
+    /// When the active [`OpenalListener`] changes, the listener pose glides from the old one to
+    /// the new one over this long instead of jumping. Zero switches instantly.
+    pub listener_blend: Duration,
 }
 
 impl Default for OpenalSettings {
     fn default() -> Self {
         Self {
             render_mode: AudioRenderMode::Auto,
+            hrtf_dataset: None,
             distance_model: DistanceModel::InverseClamped,
             max_sources: 64,
             preferred_device: None,
             muted: false,
+            master_volume: 1.0,
+            pitch_scale: 1.0,
+            bus_volumes: BusVolumes::default(),
+            velocity_smoothing: 0.5,
+            teleport_distance: 10.0,
+            listener_blend: Duration::ZERO,
         }
     }
 }
 
+impl OpenalSettings {
+    pub fn master_volume_db(&self) -> f32 {
+        db_from_gain(self.master_volume)
+    }
+
+    pub fn set_master_volume_db(&mut self, db: f32) {
+        self.master_volume = gain_from_db(db);
+    }
+}
+
 #[derive(Resource, Clone, Default)]
 pub struct OpenalStatus {
     pub available: bool,
     pub status: crate::AudioRuntimeStatus,
+    /// Latest per-voice snapshot for debug HUDs (shared, not copied).
+    pub voices: Arc<AudioVoiceSnapshot>,
+    /// The [`OpenalListener`] entity currently driving the OpenAL listener (still selected while
+    /// audio is unavailable).
+    pub active_listener: Option<Entity>,
 }
 
 #[derive(Resource)]
@@ -69,10 +161,24 @@ impl OpenalRuntime {
             distance_model: settings.distance_model,
             max_sources: settings.max_sources,
             preferred_device: settings.preferred_device.clone(),
+            hrtf_dataset: settings.hrtf_dataset.clone(),
+            ..Default::default()
         })?;
         if settings.muted {
             let _ = runtime.set_muted(true);
         }
+        if settings.master_volume != 1.0 {
+            let _ = runtime.set_master_volume(settings.master_volume);
+        }
+        if settings.pitch_scale != 1.0 {
+            let _ = runtime.set_pitch_scale(settings.pitch_scale);
+        }
+        for bus in AudioBus::ALL {
+            let volume = settings.bus_volumes.get(bus);
+            if volume != 1.0 {
+                let _ = runtime.set_bus_volume(bus, volume);
+            }
+        }
         Ok(Self { runtime })
     }
 
@@ -85,17 +191,370 @@ impl OpenalRuntime {
     }
 }
 
-#[derive(Component)]
-pub struct OpenalListener;
+/// Marks the entity whose `GlobalTransform` drives the OpenAL listener.
+///
+/// With several listeners (e.g. a player and a spectator camera), the active one with the highest
+/// `priority` wins; ties go to the lowest entity index, so the choice is stable between frames.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OpenalListener {
+    pub priority: i32,
+    pub active: bool,
+}
+
+impl Default for OpenalListener {
+    fn default() -> Self {
+        Self {
+            priority: 0,
+            active: true,
+        }
+    }
+}
+
+/// Listener selection and switch blending, carried between frames.
+#[derive(Default)]
+struct ListenerSync {
+    active: Option<Entity>,
+    velocity: VelocityEstimator,
+    /// Last pose sent; where a blend to a newly selected listener starts.
+    pose: Option<(Vec3, Quat)>,
+    blend: Option<ListenerBlend>,
+    last_sent: Option<ListenerFrame>,
+}
+
+#[derive(Copy, Clone)]
+struct ListenerBlend {
+    from: (Vec3, Quat),
+    elapsed_secs: f32,
+}
+
+impl ListenerSync {
+    /// Advances the listener to `entity`'s pose, blending from the previous listener's pose after
+    /// a switch.
+    fn update(
+        &mut self,
+        entity: Entity,
+        transform: Transform,
+        velocity_override: Option<Vec3>,
+        delta_secs: f32,
+        settings: &OpenalSettings,
+    ) -> ListenerFrame {
+        if self.active != Some(entity) {
+            let blend = !settings.listener_blend.is_zero() && self.active.is_some();
+            self.blend = self.pose.filter(|_| blend).map(|from| ListenerBlend {
+                from,
+                elapsed_secs: 0.0,
+            });
+            self.active = Some(entity);
+            self.velocity = VelocityEstimator::default();
+        }
+
+        // Velocity follows the selected entity itself; the blend glide would read as doppler.
+        let velocity = self
+            .velocity
+            .update(transform.translation, delta_secs, settings);
+        let (mut position, mut rotation) = (transform.translation, transform.rotation);
+        if let Some(blend) = self.blend.as_mut() {
+            blend.elapsed_secs += delta_secs;
+            let t = blend.elapsed_secs / settings.listener_blend.as_secs_f32().max(f32::EPSILON);
+            if t < 1.0 {
+                position = blend.from.0.lerp(position, t);
+                rotation = blend.from.1.slerp(rotation, t);
+            } else {
+                self.blend = None;
+            }
+        }
+        self.pose = Some((position, rotation));
+
+        ListenerFrame {
+            position,
+            forward: rotation.mul_vec3(Vec3::NEG_Z),
+            up: rotation.mul_vec3(Vec3::Y),
+            velocity: velocity_override.unwrap_or(velocity),
+        }
+    }
+}
+
+/// Explicit velocity for an [`OpenalListener`] or [`OpenalEmitter`] entity, e.g. from a physics
+/// body. Overrides the velocity otherwise derived from `GlobalTransform` changes.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq)]
+pub struct OpenalVelocity(pub Vec3);
+
+/// Derives velocity from successive positions, for doppler.
+#[derive(Copy, Clone, Debug, Default)]
+struct VelocityEstimator {
+    last_position: Option<Vec3>,
+    velocity: Vec3,
+}
+
+impl VelocityEstimator {
+    fn new(position: Vec3) -> Self {
+        Self {
+            last_position: Some(position),
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    fn update(&mut self, position: Vec3, delta_secs: f32, settings: &OpenalSettings) -> Vec3 {
+        let previous = self.last_position.replace(position);
+        self.velocity = match previous {
+            Some(previous) if delta_secs > 0.0 => {
+                let moved = position - previous;
+                if moved.length() > settings.teleport_distance {
+                    Vec3::ZERO
+                } else {
+                    let smoothing = settings.velocity_smoothing.clamp(0.0, 0.99);
+                    (moved / delta_secs).lerp(self.velocity, smoothing)
+                }
+            }
+            _ => Vec3::ZERO,
+        };
+        self.velocity
+    }
+}
+
+/// How long a one-shot waits for its audio asset to finish decoding before it is dropped.
+const PENDING_PLAY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What to play: a buffer registered by hand, a name from the [`SoundRegistry`], or (with
+/// `bevy-assets`) an audio asset whose buffer is created automatically.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpenalSound {
+    Buffer(BufferKey),
+    Named(String),
+    #[cfg(feature = "bevy-assets")]
+    Asset(Handle<OpenalAudioBytes>),
+    #[cfg(feature = "bevy-assets")]
+    Decoded(Handle<OpenalDecodedAudio>),
+}
+
+impl From<BufferKey> for OpenalSound {
+    fn from(key: BufferKey) -> Self {
+        OpenalSound::Buffer(key)
+    }
+}
+
+impl From<String> for OpenalSound {
+    fn from(name: String) -> Self {
+        OpenalSound::Named(name)
+    }
+}
+
+impl From<&str> for OpenalSound {
+    fn from(name: &str) -> Self {
+        OpenalSound::Named(name.to_string())
+    }
+}
+
+#[cfg(feature = "bevy-assets")]
+impl From<Handle<OpenalAudioBytes>> for OpenalSound {
+    fn from(handle: Handle<OpenalAudioBytes>) -> Self {
+        OpenalSound::Asset(handle)
+    }
+}
+
+#[cfg(feature = "bevy-assets")]
+impl From<Handle<OpenalDecodedAudio>> for OpenalSound {
+    fn from(handle: Handle<OpenalDecodedAudio>) -> Self {
+        OpenalSound::Decoded(handle)
+    }
+}
+
+// Only asset sounds can be pending.
+#[cfg_attr(not(feature = "bevy-assets"), allow(dead_code))]
+enum SoundKey {
+    Ready(BufferKey),
+    /// The asset is still loading or decoding.
+    Pending,
+    Failed(String),
+}
+
+/// Resolves an [`OpenalSound`] to the runtime buffer it plays.
+#[derive(SystemParam)]
+struct SoundKeys<'w> {
+    names: Option<Res<'w, SoundRegistry>>,
+    #[cfg(feature = "bevy-assets")]
+    assets: Option<Res<'w, OpenalAudioBuffers>>,
+}
+
+impl SoundKeys<'_> {
+    fn resolve(&self, sound: &OpenalSound) -> SoundKey {
+        match sound {
+            OpenalSound::Buffer(key) => SoundKey::Ready(*key),
+            OpenalSound::Named(name) => {
+                let Some(names) = self.names.as_deref() else {
+                    return SoundKey::Failed("no SoundRegistry resource".to_string());
+                };
+                match names.key(name) {
+                    Ok(key) => SoundKey::Ready(key),
+                    Err(err) => SoundKey::Failed(err.to_string()),
+                }
+            }
+            #[cfg(feature = "bevy-assets")]
+            OpenalSound::Asset(handle) => self.resolve_asset(handle.into()),
+            #[cfg(feature = "bevy-assets")]
+            OpenalSound::Decoded(handle) => self.resolve_asset(handle.into()),
+        }
+    }
+
+    #[cfg(feature = "bevy-assets")]
+    fn resolve_asset(&self, id: UntypedAssetId) -> SoundKey {
+        let Some(assets) = self.assets.as_deref() else {
+            return SoundKey::Failed("BevyOpenalAssetsPlugin is not installed".to_string());
+        };
+        match (assets.key(id), assets.state(id)) {
+            (Some(key), Some(state)) if state.is_playable() => SoundKey::Ready(key),
+            (_, Some(AudioBufferState::Failed)) => {
+                SoundKey::Failed("audio asset failed to decode".to_string())
+            }
+            _ => SoundKey::Pending,
+        }
+    }
+}
 
-#[derive(Message, Copy, Clone)]
+#[derive(Message, Clone)]
 pub struct OpenalPlayOneShot {
-    pub key: BufferKey,
+    pub sound: OpenalSound,
     pub position: Vec3,
     pub gain: f32,
     pub pitch: f32,
 }
 
+/// A sound carried by an entity.
+///
+/// When the component is inserted (and `autoplay` is set) a voice starts at the entity's
+/// `GlobalTransform`, as soon as an asset sound has finished decoding; its position and velocity
+/// then follow the entity every frame. Removing the
+/// component or despawning the entity stops the voice after `fade_out`. The running voice is
+/// exposed through [`OpenalEmitterVoice`]; a one-shot that finishes, or a voice that is stolen or
+/// lost with the device, removes it. A looping emitter lost with the device (a reopen, see
+/// [`OpenalSettings::max_sources`]) starts a new voice; a stolen one stays stopped. Write an [`OpenalEmitterControl`] message to play or stop the
+/// emitter later (for example with `autoplay` off), or re-insert the emitter to replace its voice.
+#[derive(Component, Clone, Debug)]
+#[component(on_insert = queue_inserted_emitter)]
+pub struct OpenalEmitter {
+    pub sound: OpenalSound,
+    pub autoplay: bool,
+    pub looping: bool,
+    pub gain: f32,
+    pub pitch: f32,
+    pub bus: AudioBus,
+    pub priority: i32,
+    pub fade_in: Duration,
+    pub fade_out: Duration,
+}
+
+impl OpenalEmitter {
+    /// Autoplaying, non-looping emitter at unit gain and pitch.
+    pub fn new(sound: impl Into<OpenalSound>) -> Self {
+        Self {
+            sound: sound.into(),
+            autoplay: true,
+            looping: false,
+            gain: 1.0,
+            pitch: 1.0,
+            bus: AudioBus::Sfx,
+            priority: 0,
+            fade_in: Duration::ZERO,
+            fade_out: Duration::from_millis(20),
+        }
+    }
+}
+
+/// Starts or stops the voice of an [`OpenalEmitter`] entity.
+///
+/// `Play` restarts the sound if it is already playing; `Stop` fades it out over the emitter's
+/// `fade_out`. Both are handled in the frame they are written, after emitters added that frame.
+#[derive(Message, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OpenalEmitterControl {
+    Play(Entity),
+    Stop(Entity),
+}
+
+/// The voice currently playing for an [`OpenalEmitter`] on the same entity.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OpenalEmitterVoice(pub VoiceId);
+
+#[derive(Copy, Clone, Debug)]
+struct EmitterVoice {
+    voice: VoiceId,
+    fade_out: Duration,
+    velocity: VelocityEstimator,
+    /// Last transform submitted, to skip redundant updates.
+    last_sent: (Vec3, Vec3),
+}
+
+/// Voices started for emitters, keyed by entity so they can be stopped after a despawn, and
+/// emitters still waiting for their sound to become playable.
+#[derive(Resource, Default)]
+struct EmitterVoices {
+    playing: HashMap<Entity, EmitterVoice>,
+    pending: HashSet<Entity>,
+    /// Emitters inserted since the last frame, including replacements of an existing emitter,
+    /// which `Added` does not report.
+    inserted: Vec<Entity>,
+}
+
+fn queue_inserted_emitter(mut world: DeferredWorld, context: HookContext) {
+    if let Some(mut voices) = world.get_resource_mut::<EmitterVoices>() {
+        voices.inserted.push(context.entity);
+    }
+}
+
+impl EmitterVoices {
+    /// Stops the entity's voice, if any, and cancels a pending start.
+    fn stop(&mut self, entity: Entity, runtime: &AudioRuntime) -> bool {
+        self.pending.remove(&entity);
+        let Some(emitter) = self.playing.remove(&entity) else {
+            return false;
+        };
+        let _ = runtime.stop_voice(emitter.voice, emitter.fade_out);
+        true
+    }
+}
+
+/// Playback lifecycle event republished from the audio thread (voice started/finished/stolen,
+/// play failures, device loss).
+#[derive(Message, Clone, Debug)]
+pub struct OpenalAudioEvent(pub AudioEvent);
+
+/// A one-shot or emitter sound that could not be played because it did not resolve to a buffer:
+/// an unknown [`OpenalSound::Named`] name, a failed or missing asset, or an asset that did not
+/// finish decoding in time.
+#[derive(Message, Clone, Debug)]
+pub struct OpenalSoundFailed {
+    pub sound: OpenalSound,
+    /// The [`OpenalEmitter`] entity, or `None` for an [`OpenalPlayOneShot`].
+    pub emitter: Option<Entity>,
+    pub reason: String,
+}
+
+/// A runtime-backed [`OpenalSettings`] field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OpenalSetting {
+    RenderMode,
+    HrtfDataset,
+    DistanceModel,
+    Muted,
+    MasterVolume,
+    PitchScale,
+    BusVolume(AudioBus),
+    MaxSources,
+    PreferredDevice,
+}
+
+/// Outcome of applying a changed [`OpenalSettings`] field to the running runtime.
+///
+/// The velocity and listener-blend fields are read every frame and need no apply step, so they
+/// are not reported.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub enum OpenalSettingsEvent {
+    Applied(OpenalSetting),
+    Failed {
+        setting: OpenalSetting,
+        reason: String,
+    },
+}
+
 fn init_openal_runtime(mut commands: Commands, settings: Res<OpenalSettings>) {
     match OpenalRuntime::new(&settings) {
         Ok(runtime) => {
@@ -107,137 +566,706 @@ fn init_openal_runtime(mut commands: Commands, settings: Res<OpenalSettings>) {
     }
 }
 
-#[derive(Copy, Clone, Debug, Default)]
+/// Runtime-side values last submitted for each [`OpenalSetting`].
+#[derive(Clone, Debug)]
 struct AppliedSettings {
     render_mode: AudioRenderMode,
+    hrtf_dataset: Option<String>,
     distance_model: DistanceModel,
     muted: bool,
+    master_volume: f32,
+    pitch_scale: f32,
+    bus_volumes: BusVolumes,
+    max_sources: usize,
+    preferred_device: Option<String>,
+}
+
+impl From<&OpenalSettings> for AppliedSettings {
+    fn from(settings: &OpenalSettings) -> Self {
+        Self {
+            render_mode: settings.render_mode,
+            hrtf_dataset: settings.hrtf_dataset.clone(),
+            distance_model: settings.distance_model,
+            muted: settings.muted,
+            master_volume: settings.master_volume,
+            pitch_scale: settings.pitch_scale,
+            bus_volumes: settings.bus_volumes,
+            max_sources: settings.max_sources,
+            preferred_device: settings.preferred_device.clone(),
+        }
+    }
 }
 
 fn apply_settings_system(
     settings: Res<OpenalSettings>,
     runtime: Option<Res<OpenalRuntime>>,
     mut applied: Local<Option<AppliedSettings>>,
+    mut in_flight: Local<Vec<(OpenalSetting, AudioTicket)>>,
+    mut events: MessageWriter<OpenalSettingsEvent>,
 ) {
     let Some(runtime) = runtime else {
         *applied = None;
+        in_flight.clear();
         return;
     };
+
+    in_flight.retain(|(setting, ticket)| {
+        let Some(result) = ticket.try_result() else {
+            return true;
+        };
+        let setting = *setting;
+        events.write(match result {
+            Ok(()) => OpenalSettingsEvent::Applied(setting),
+            Err(err) => {
+                warn!(?setting, error = %err, "Failed to apply OpenAL setting");
+                OpenalSettingsEvent::Failed {
+                    setting,
+                    reason: err.to_string(),
+                }
+            }
+        });
+        false
+    });
+
     if runtime.runtime().is_shutdown_requested() {
         return;
     }
-
     if applied.is_none() {
-        *applied = Some(AppliedSettings {
-            render_mode: settings.render_mode,
-            distance_model: settings.distance_model,
-            muted: settings.muted,
-        });
+        *applied = Some(AppliedSettings::from(&*settings));
         return;
     }
-
     if !settings.is_changed() {
         return;
     }
-
-    let Some(mut applied_settings) = *applied else {
+    let Some(applied) = applied.as_mut() else {
         return;
     };
 
-    if settings.muted != applied_settings.muted {
-        if runtime.runtime().set_muted(settings.muted).is_err() {
-            warn!("Failed to apply OpenAL mute");
-        } else {
-            applied_settings.muted = settings.muted;
-        }
-    }
+    let handle = runtime.runtime().handle();
+    let mut submit =
+        |setting: OpenalSetting, command: AudioCommand| match handle.submit_acked(command) {
+            Ok(ticket) => {
+                in_flight.push((setting, ticket));
+                true
+            }
+            Err(err) => {
+                warn!(?setting, error = %err, "Failed to apply OpenAL setting");
+                events.write(OpenalSettingsEvent::Failed {
+                    setting,
+                    reason: err.to_string(),
+                });
+                false
+            }
+        };
 
-    if settings.distance_model != applied_settings.distance_model {
-        if runtime
-            .runtime()
-            .set_distance_model(settings.distance_model)
-            .is_err()
-        {
-            warn!("Failed to apply OpenAL distance model");
-        } else {
-            applied_settings.distance_model = settings.distance_model;
-        }
+    if settings.muted != applied.muted
+        && submit(OpenalSetting::Muted, AudioCommand::SetMuted(settings.muted))
+    {
+        applied.muted = settings.muted;
     }
-
-    if settings.render_mode != applied_settings.render_mode {
-        if runtime
-            .runtime()
-            .set_render_mode(settings.render_mode)
-            .is_err()
+    if settings.master_volume != applied.master_volume
+        && submit(
+            OpenalSetting::MasterVolume,
+            AudioCommand::SetMasterVolume(settings.master_volume),
+        )
+    {
+        applied.master_volume = settings.master_volume;
+    }
+    if settings.pitch_scale != applied.pitch_scale
+        && submit(
+            OpenalSetting::PitchScale,
+            AudioCommand::SetPitchScale(settings.pitch_scale),
+        )
+    {
+        applied.pitch_scale = settings.pitch_scale;
+    }
+    for bus in AudioBus::ALL {
+        let volume = settings.bus_volumes.get(bus);
+        if volume != applied.bus_volumes.get(bus)
+            && submit(
+                OpenalSetting::BusVolume(bus),
+                AudioCommand::SetBusVolume { bus, volume },
+            )
         {
-            warn!("Failed to apply OpenAL render mode");
-        } else {
-            applied_settings.render_mode = settings.render_mode;
+            applied.bus_volumes.set(bus, volume);
         }
     }
-
-    *applied = Some(applied_settings);
+    if settings.distance_model != applied.distance_model
+        && submit(
+            OpenalSetting::DistanceModel,
+            AudioCommand::SetDistanceModel(settings.distance_model),
+        )
+    {
+        applied.distance_model = settings.distance_model;
+    }
+    if settings.max_sources != applied.max_sources
+        && submit(
+            OpenalSetting::MaxSources,
+            AudioCommand::SetMaxSources(settings.max_sources),
+        )
+    {
+        applied.max_sources = settings.max_sources;
+    }
+    if settings.preferred_device != applied.preferred_device
+        && submit(
+            OpenalSetting::PreferredDevice,
+            AudioCommand::SetPreferredDevice(settings.preferred_device.clone()),
+        )
+    {
+        applied.preferred_device = settings.preferred_device.clone();
+    }
+    if settings.render_mode != applied.render_mode
+        && submit(
+            OpenalSetting::RenderMode,
+            AudioCommand::SetRenderMode(settings.render_mode),
+        )
+    {
+        applied.render_mode = settings.render_mode;
+    }
+    if settings.hrtf_dataset != applied.hrtf_dataset
+        && submit(
+            OpenalSetting::HrtfDataset,
+            AudioCommand::SetHrtfDataset(settings.hrtf_dataset.clone()),
+        )
+    {
+        applied.hrtf_dataset = settings.hrtf_dataset.clone();
+    }
 }
 
 fn sync_status_system(runtime: Option<Res<OpenalRuntime>>, mut status: ResMut<OpenalStatus>) {
     let Some(runtime) = runtime else {
         status.available = false;
         status.status = Default::default();
+        status.voices = Default::default();
         return;
     };
 
     status.available = true;
     status.status = runtime.runtime().status();
+    status.voices = runtime.runtime().voice_snapshot();
 }
 
 fn sync_listener_system(
-    listener_query: Query<&GlobalTransform, With<OpenalListener>>,
+    listener_query: Query<(
+        Entity,
+        &OpenalListener,
+        &GlobalTransform,
+        Option<&OpenalVelocity>,
+    )>,
     runtime: Option<Res<OpenalRuntime>>,
+    settings: Res<OpenalSettings>,
+    time: Option<Res<Time>>,
+    mut status: ResMut<OpenalStatus>,
+    mut sync: Local<ListenerSync>,
 ) {
-    let Some(runtime) = runtime else {
-        return;
-    };
-    if runtime.runtime().is_shutdown_requested() {
-        return;
+    let selected = listener_query
+        .iter()
+        .filter(|(_, listener, ..)| listener.active)
+        .max_by_key(|(entity, listener, ..)| (listener.priority, Reverse(entity.index())));
+    if status.active_listener != selected.map(|(entity, ..)| entity) {
+        status.active_listener = selected.map(|(entity, ..)| entity);
     }
-    let Some(transform) = listener_query.iter().next() else {
+    let Some((entity, _, transform, velocity_override)) = selected else {
+        sync.active = None;
+        sync.blend = None;
         return;
     };
 
-    let transform = transform.compute_transform();
-    let forward = transform.rotation.mul_vec3(Vec3::NEG_Z);
-    let up = transform.rotation.mul_vec3(Vec3::Y);
-    let listener = ListenerFrame {
-        position: transform.translation,
-        forward,
-        up,
-        velocity: Vec3::ZERO,
+    let delta_secs = time.map_or(0.0, |time| time.delta_secs());
+    let listener = sync.update(
+        entity,
+        transform.compute_transform(),
+        velocity_override.map(|velocity| velocity.0),
+        delta_secs,
+        &settings,
+    );
+
+    // Selection runs without audio too; a new runtime gets the current pose.
+    let runtime = runtime.filter(|runtime| !runtime.runtime().is_shutdown_requested());
+    let Some(runtime) = runtime else {
+        sync.last_sent = None;
+        return;
     };
+    if sync.last_sent == Some(listener) {
+        return;
+    }
     if runtime.runtime().set_listener(listener).is_err() {
         warn!("Failed to update OpenAL listener");
+    } else {
+        sync.last_sent = Some(listener);
     }
 }
 
 fn play_one_shot_system(
     mut messages: MessageReader<OpenalPlayOneShot>,
     runtime: Option<Res<OpenalRuntime>>,
+    sounds: SoundKeys,
+    mut failed: MessageWriter<OpenalSoundFailed>,
+    mut pending: Local<Vec<(OpenalPlayOneShot, Instant)>>,
 ) {
+    let runtime = runtime.filter(|runtime| !runtime.runtime().is_shutdown_requested());
     let Some(runtime) = runtime else {
-        messages.clear();
+        // Nothing plays without audio, but sounds that could never play are still reported.
+        for event in messages.read() {
+            if let SoundKey::Failed(reason) = sounds.resolve(&event.sound) {
+                failed.write(OpenalSoundFailed {
+                    sound: event.sound.clone(),
+                    emitter: None,
+                    reason,
+                });
+            }
+        }
+        pending.clear();
         return;
     };
-    if runtime.runtime().is_shutdown_requested() {
-        messages.clear();
-        return;
-    }
-    for event in messages.read() {
+    let now = Instant::now();
+    let queued = std::mem::take(&mut *pending);
+    let arrived = messages.read().cloned().map(|event| (event, now));
+    for (event, requested_at) in queued.into_iter().chain(arrived) {
+        let key = match sounds.resolve(&event.sound) {
+            SoundKey::Ready(key) => key,
+            SoundKey::Pending if now.duration_since(requested_at) < PENDING_PLAY_TIMEOUT => {
+                pending.push((event, requested_at));
+                continue;
+            }
+            SoundKey::Pending => {
+                warn!(sound = ?event.sound, "OpenAL one-shot dropped waiting for its audio asset");
+                failed.write(OpenalSoundFailed {
+                    sound: event.sound,
+                    emitter: None,
+                    reason: "timed out waiting for the audio asset".to_string(),
+                });
+                continue;
+            }
+            SoundKey::Failed(reason) => {
+                warn!(sound = ?event.sound, %reason, "OpenAL one-shot sound is not playable");
+                failed.write(OpenalSoundFailed {
+                    sound: event.sound,
+                    emitter: None,
+                    reason,
+                });
+                continue;
+            }
+        };
         let params = PlayOneShotParams {
             position: event.position,
             gain: event.gain,
             pitch: event.pitch,
+            ..Default::default()
         };
-        if runtime.runtime().play_one_shot(event.key, params).is_err() {
+        if runtime.runtime().play_one_shot(key, params).is_err() {
             warn!("Failed to play OpenAL one-shot");
         }
     }
 }
+
+fn stop_removed_emitters_system(
+    mut removed: RemovedComponents<OpenalEmitter>,
+    mut voices: ResMut<EmitterVoices>,
+    runtime: Option<Res<OpenalRuntime>>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        voices.pending.remove(&entity);
+        let Some(emitter) = voices.playing.remove(&entity) else {
+            continue;
+        };
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_remove::<OpenalEmitterVoice>();
+        }
+        if let Some(runtime) = runtime.as_deref() {
+            let _ = runtime
+                .runtime()
+                .stop_voice(emitter.voice, emitter.fade_out);
+        }
+    }
+}
+
+/// Queues newly inserted autoplay emitters and applies [`OpenalEmitterControl`] messages.
+fn control_emitters_system(
+    emitters: Query<&OpenalEmitter>,
+    mut controls: MessageReader<OpenalEmitterControl>,
+    mut voices: ResMut<EmitterVoices>,
+    runtime: Option<Res<OpenalRuntime>>,
+    mut commands: Commands,
+) {
+    let runtime = runtime.filter(|runtime| !runtime.runtime().is_shutdown_requested());
+    let Some(runtime) = runtime else {
+        controls.clear();
+        voices.inserted.clear();
+        return;
+    };
+    let inserted = std::mem::take(&mut voices.inserted);
+    for entity in inserted {
+        let Ok(emitter) = emitters.get(entity) else {
+            continue;
+        };
+        // Re-inserting the component replaces the previous voice.
+        if voices.stop(entity, runtime.runtime()) && !emitter.autoplay {
+            commands.entity(entity).try_remove::<OpenalEmitterVoice>();
+        }
+        if emitter.autoplay {
+            voices.pending.insert(entity);
+        }
+    }
+    for control in controls.read() {
+        match *control {
+            OpenalEmitterControl::Play(entity) => {
+                voices.stop(entity, runtime.runtime());
+                voices.pending.insert(entity);
+            }
+            OpenalEmitterControl::Stop(entity) => {
+                if voices.stop(entity, runtime.runtime()) {
+                    if let Ok(mut entity) = commands.get_entity(entity) {
+                        entity.try_remove::<OpenalEmitterVoice>();
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn start_emitters_system(
+    emitters: Query<(&OpenalEmitter, Option<&GlobalTransform>)>,
+    mut voices: ResMut<EmitterVoices>,
+    runtime: Option<Res<OpenalRuntime>>,
+    sounds: SoundKeys,
+    mut failed: MessageWriter<OpenalSoundFailed>,
+    mut commands: Commands,
+) {
+    let Some(runtime) = runtime else {
+        return;
+    };
+    if runtime.runtime().is_shutdown_requested() {
+        return;
+    }
+    let pending: Vec<Entity> = voices.pending.iter().copied().collect();
+    for entity in pending {
+        let Ok((emitter, transform)) = emitters.get(entity) else {
+            voices.pending.remove(&entity);
+            continue;
+        };
+        let key = match sounds.resolve(&emitter.sound) {
+            SoundKey::Ready(key) => key,
+            SoundKey::Pending => continue,
+            SoundKey::Failed(reason) => {
+                warn!(sound = ?emitter.sound, %reason, "OpenAL emitter sound is not playable");
+                failed.write(OpenalSoundFailed {
+                    sound: emitter.sound.clone(),
+                    emitter: Some(entity),
+                    reason,
+                });
+                voices.pending.remove(&entity);
+                continue;
+            }
+        };
+        voices.pending.remove(&entity);
+
+        let position = transform.map_or(Vec3::ZERO, GlobalTransform::translation);
+        let params = PlayOneShotParams {
+            position,
+            gain: emitter.gain,
+            pitch: emitter.pitch,
+            bus: emitter.bus,
+            priority: emitter.priority,
+            fade_in: emitter.fade_in,
+            looping: emitter.looping,
+        };
+        match runtime.runtime().play_one_shot(key, params) {
+            Ok(voice) => {
+                voices.playing.insert(
+                    entity,
+                    EmitterVoice {
+                        voice,
+                        fade_out: emitter.fade_out,
+                        velocity: VelocityEstimator::new(position),
+                        last_sent: (position, Vec3::ZERO),
+                    },
+                );
+                commands
+                    .entity(entity)
+                    .try_insert(OpenalEmitterVoice(voice));
+            }
+            Err(err) => warn!(error = %err, key, "Failed to start OpenAL emitter"),
+        }
+    }
+}
+
+fn sync_emitters_system(
+    emitters: Query<(Entity, &GlobalTransform, Option<&OpenalVelocity>), With<OpenalEmitter>>,
+    mut voices: ResMut<EmitterVoices>,
+    runtime: Option<Res<OpenalRuntime>>,
+    settings: Res<OpenalSettings>,
+    time: Option<Res<Time>>,
+) {
+    let Some(runtime) = runtime else {
+        return;
+    };
+    if runtime.runtime().is_shutdown_requested() {
+        return;
+    }
+    let delta_secs = time.map_or(0.0, |time| time.delta_secs());
+    for (entity, transform, velocity_override) in &emitters {
+        let Some(emitter) = voices.playing.get_mut(&entity) else {
+            continue;
+        };
+        let position = transform.translation();
+        let velocity = emitter.velocity.update(position, delta_secs, &settings);
+        let velocity = velocity_override.map_or(velocity, |velocity| velocity.0);
+        if (position, velocity) == emitter.last_sent {
+            continue;
+        }
+        // Transform updates are coalesced per voice, so one submit per frame is cheap.
+        if runtime
+            .runtime()
+            .set_voice_transform(emitter.voice, position, velocity)
+            .is_ok()
+        {
+            emitter.last_sent = (position, velocity);
+        }
+    }
+}
+
+/// Forgets emitter voices that ended on their own (finished, stolen, failed or lost with the
+/// device), and restarts looping emitters whose voice was lost with the device.
+fn release_finished_emitters_system(
+    mut events: MessageReader<OpenalAudioEvent>,
+    emitters: Query<&OpenalEmitter>,
+    mut voices: ResMut<EmitterVoices>,
+    mut commands: Commands,
+) {
+    for OpenalAudioEvent(event) in events.read() {
+        let ended = match event {
+            AudioEvent::VoiceFinished { voice, .. }
+            | AudioEvent::VoiceStolen { voice, .. }
+            | AudioEvent::VoiceDropped { voice, .. }
+            | AudioEvent::PlayFailed { voice, .. } => *voice,
+            _ => continue,
+        };
+        let Some(entity) = voices
+            .playing
+            .iter()
+            .find_map(|(entity, emitter)| (emitter.voice == ended).then_some(*entity))
+        else {
+            continue;
+        };
+        voices.playing.remove(&entity);
+        // A reopen cuts every emitter voice; loops start again, one-shots stay finished.
+        if matches!(event, AudioEvent::VoiceDropped { .. })
+            && emitters.get(entity).is_ok_and(|emitter| emitter.looping)
+        {
+            voices.pending.insert(entity);
+        }
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_remove::<OpenalEmitterVoice>();
+        }
+    }
+}
+
+fn publish_events_system(
+    runtime: Option<Res<OpenalRuntime>>,
+    mut writer: MessageWriter<OpenalAudioEvent>,
+) {
+    let Some(runtime) = runtime else {
+        return;
+    };
+    writer.write_batch(
+        runtime
+            .runtime()
+            .drain_events()
+            .into_iter()
+            .map(OpenalAudioEvent),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::message::Messages;
+    use bevy_ecs::prelude::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::Vec3;
+
+    use bevy_transform::components::{GlobalTransform, Transform};
+    use std::time::Duration;
+
+    use super::{
+        control_emitters_system, play_one_shot_system, release_finished_emitters_system,
+        start_emitters_system, sync_listener_system, EmitterVoices, ListenerSync, OpenalAudioEvent,
+        OpenalEmitter, OpenalEmitterControl, OpenalEmitterVoice, OpenalListener, OpenalPlayOneShot,
+        OpenalRuntime, OpenalSettings, OpenalSound, OpenalSoundFailed, OpenalStatus,
+        VelocityEstimator,
+    };
+    use crate::{AudioEvent, SoundRegistry};
+
+    #[test]
+    fn highest_priority_active_listener_wins() {
+        let mut world = World::new();
+        world.init_resource::<OpenalSettings>();
+        world.init_resource::<OpenalStatus>();
+        let listener = |priority, active| {
+            (
+                OpenalListener { priority, active },
+                GlobalTransform::default(),
+            )
+        };
+        world.spawn(listener(0, true));
+        let inactive = world.spawn(listener(2, false)).id();
+        let tied_first = world.spawn(listener(1, true)).id();
+        world.spawn(listener(1, true));
+
+        world.run_system_once(sync_listener_system).unwrap();
+        let active = world.resource::<OpenalStatus>().active_listener;
+        assert_eq!(active, Some(tied_first));
+
+        world.get_mut::<OpenalListener>(inactive).unwrap().active = true;
+        world.run_system_once(sync_listener_system).unwrap();
+        let active = world.resource::<OpenalStatus>().active_listener;
+        assert_eq!(active, Some(inactive));
+    }
+
+    #[test]
+    fn listener_switch_blends_from_the_previous_pose() {
+        let settings = OpenalSettings {
+            listener_blend: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let mut world = World::new();
+        let (first, second) = (world.spawn_empty().id(), world.spawn_empty().id());
+        let mut sync = ListenerSync::default();
+
+        let frame = sync.update(first, Transform::default(), None, 0.1, &settings);
+        assert_eq!(frame.position, Vec3::ZERO);
+
+        let target = Transform::from_xyz(10.0, 0.0, 0.0);
+        let frame = sync.update(second, target, None, 0.5, &settings);
+        assert!((frame.position.x - 5.0).abs() < 1e-4, "{}", frame.position);
+        let frame = sync.update(second, target, None, 0.5, &settings);
+        assert_eq!(frame.position, target.translation);
+        assert!(sync.blend.is_none());
+    }
+
+    #[test]
+    fn reinserting_an_emitter_restarts_its_voice() {
+        let mut world = World::new();
+        world.init_resource::<EmitterVoices>();
+        world.init_resource::<Messages<OpenalEmitterControl>>();
+        world.init_resource::<Messages<OpenalSoundFailed>>();
+        // Without an OpenAL library the runtime still hands out voice ids.
+        let runtime = OpenalRuntime::new(&OpenalSettings::default()).unwrap();
+        world.insert_resource(runtime);
+        let emit = |world: &mut World| {
+            world.run_system_once(control_emitters_system).unwrap();
+            world.run_system_once(start_emitters_system).unwrap();
+        };
+
+        let entity = world.spawn(OpenalEmitter::new(OpenalSound::Buffer(1))).id();
+        emit(&mut world);
+        let first = world.get::<OpenalEmitterVoice>(entity).copied().unwrap();
+
+        world
+            .entity_mut(entity)
+            .insert(OpenalEmitter::new(OpenalSound::Buffer(2)));
+        emit(&mut world);
+        let second = world.get::<OpenalEmitterVoice>(entity).copied().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(world.resource::<EmitterVoices>().playing.len(), 1);
+
+        // Changing the emitter in place does not restart it.
+        world.get_mut::<OpenalEmitter>(entity).unwrap().gain = 0.5;
+        emit(&mut world);
+        assert_eq!(world.get::<OpenalEmitterVoice>(entity), Some(&second));
+    }
+
+    #[test]
+    fn looping_emitters_restart_after_a_device_reopen() {
+        let mut world = World::new();
+        world.init_resource::<EmitterVoices>();
+        world.init_resource::<Messages<OpenalEmitterControl>>();
+        world.init_resource::<Messages<OpenalSoundFailed>>();
+        world.init_resource::<Messages<OpenalAudioEvent>>();
+        let runtime = OpenalRuntime::new(&OpenalSettings::default()).unwrap();
+        world.insert_resource(runtime);
+        let emit = |world: &mut World| {
+            world.run_system_once(control_emitters_system).unwrap();
+            world.run_system_once(start_emitters_system).unwrap();
+        };
+        let voice = |world: &World, entity| world.get::<OpenalEmitterVoice>(entity).map(|v| v.0);
+
+        let looping = world
+            .spawn(OpenalEmitter {
+                looping: true,
+                ..OpenalEmitter::new(OpenalSound::Buffer(1))
+            })
+            .id();
+        let one_shot = world.spawn(OpenalEmitter::new(OpenalSound::Buffer(1))).id();
+        emit(&mut world);
+        let (loop_voice, shot_voice) = (voice(&world, looping), voice(&world, one_shot));
+
+        for voice in [loop_voice, shot_voice].into_iter().flatten() {
+            world.write_message(OpenalAudioEvent(AudioEvent::VoiceDropped { voice, key: 1 }));
+        }
+        world
+            .run_system_once(release_finished_emitters_system)
+            .unwrap();
+        emit(&mut world);
+        let restarted = voice(&world, looping);
+        assert!(restarted.is_some() && restarted != loop_voice);
+        assert_eq!(voice(&world, one_shot), None);
+
+        // A stolen loop stays stopped.
+        let stolen = restarted.unwrap();
+        world.write_message(OpenalAudioEvent(AudioEvent::VoiceStolen {
+            voice: stolen,
+            key: 1,
+        }));
+        world
+            .run_system_once(release_finished_emitters_system)
+            .unwrap();
+        emit(&mut world);
+        assert_eq!(voice(&world, looping), None);
+    }
+
+    #[test]
+    fn unknown_sound_names_are_reported() {
+        let mut world = World::new();
+        world.init_resource::<SoundRegistry>();
+        world.init_resource::<Messages<OpenalPlayOneShot>>();
+        world.init_resource::<Messages<OpenalSoundFailed>>();
+        let sound = OpenalSound::Named("missing".to_string());
+
+        world.write_message(OpenalPlayOneShot {
+            sound: sound.clone(),
+            position: Vec3::ZERO,
+            gain: 1.0,
+            pitch: 1.0,
+        });
+        world.run_system_once(play_one_shot_system).unwrap();
+
+        let failed: Vec<OpenalSoundFailed> = world
+            .resource_mut::<Messages<OpenalSoundFailed>>()
+            .drain()
+            .collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].sound, sound);
+        assert_eq!(failed[0].emitter, None);
+        assert!(failed[0].reason.contains("missing"), "{}", failed[0].reason);
+    }
+
+    #[test]
+    fn velocity_estimator_smooths_and_ignores_teleports() {
+        let settings = OpenalSettings {
+            velocity_smoothing: 0.5,
+            teleport_distance: 5.0,
+            ..Default::default()
+        };
+        let mut estimator = VelocityEstimator::new(Vec3::ZERO);
+
+        let velocity = estimator.update(Vec3::new(1.0, 0.0, 0.0), 0.1, &settings);
+        assert!((velocity.x - 5.0).abs() < 1e-4, "{velocity}");
+        let velocity = estimator.update(Vec3::new(2.0, 0.0, 0.0), 0.1, &settings);
+        assert!((velocity.x - 7.5).abs() < 1e-4, "{velocity}");
+
+        let velocity = estimator.update(Vec3::new(100.0, 0.0, 0.0), 0.1, &settings);
+        assert_eq!(velocity, Vec3::ZERO);
+    }
+}
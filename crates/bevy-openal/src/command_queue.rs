@@ -0,0 +1,309 @@
+use glam::Vec3;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use crate::runtime::CommandOutput;
+use crate::{AudioCommand, ListenerFrame, RuntimeError, VoiceId};
+
+/// Completion callback for an acknowledged command; the audio thread calls it exactly once.
+/// Dropping it unanswered (e.g. when the queue closes) resolves the ticket as stopped.
+pub(crate) type Ack = Box<dyn FnOnce(Result<CommandOutput, RuntimeError>) + Send>;
+
+/// Work for the audio thread: a public command, or a query that only exists to fill a ticket.
+pub(crate) enum Request {
+    Command(AudioCommand),
+    VoiceOffset(VoiceId),
+}
+
+/// A request as delivered to the audio thread, with its optional completion channel.
+pub(crate) struct QueuedCommand {
+    pub request: Request,
+    pub ack: Option<Ack>,
+}
+
+impl From<AudioCommand> for QueuedCommand {
+    fn from(command: AudioCommand) -> Self {
+        Self {
+            request: Request::Command(command),
+            ack: None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum QueueError {
+    Full,
+    Closed,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct QueueStats {
+    pub depth: usize,
+    pub coalesced: u64,
+    pub dropped: u64,
+}
+
+/// Bounded command queue shared between submitters and the audio thread.
+///
+/// Ordinary commands are delivered in FIFO order and rejected once `capacity` are pending.
+/// High-frequency state updates (listener frame, per-voice transforms) are held in slots where a
+/// newer update replaces the pending one, so only the latest value per target reaches the audio
+/// thread. Slots are delivered after the FIFO commands of the same batch, which keeps a transform
+/// from arriving before the play command that created its voice. Acknowledged commands are never
+/// coalesced, since every submitter is owed its own result; an acknowledged update discards the
+/// older pending value for its target so that value cannot land after it.
+pub(crate) struct CommandQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+    capacity: usize,
+    coalesced: AtomicU64,
+    dropped: AtomicU64,
+}
+
+#[derive(Default)]
+struct QueueState {
+    commands: VecDeque<QueuedCommand>,
+    listener: Option<ListenerFrame>,
+    voice_transforms: HashMap<VoiceId, (Vec3, Vec3)>,
+    closed: bool,
+}
+
+impl CommandQueue {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            ready: Condvar::new(),
+            capacity: capacity.max(1),
+            coalesced: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn push(&self, command: AudioCommand) -> Result<(), QueueError> {
+        self.push_inner(Request::Command(command), None)
+    }
+
+    /// Queues `request` in FIFO order; `ack` receives the result once the audio thread ran it.
+    pub(crate) fn push_acked(&self, request: Request, ack: Ack) -> Result<(), QueueError> {
+        self.push_inner(request, Some(ack))
+    }
+
+    fn push_inner(&self, request: Request, ack: Option<Ack>) -> Result<(), QueueError> {
+        let Ok(mut state) = self.state.lock() else {
+            return Err(QueueError::Closed);
+        };
+        if state.closed {
+            return Err(QueueError::Closed);
+        }
+
+        match request {
+            request if ack.is_some() => {
+                if state.commands.len() >= self.capacity {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(QueueError::Full);
+                }
+                let superseded = match &request {
+                    Request::Command(AudioCommand::SetListener(_)) => {
+                        state.listener.take().is_some()
+                    }
+                    Request::Command(AudioCommand::SetVoiceTransform { voice, .. }) => {
+                        state.voice_transforms.remove(voice).is_some()
+                    }
+                    _ => false,
+                };
+                if superseded {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                }
+                state.commands.push_back(QueuedCommand { request, ack });
+            }
+            Request::Command(AudioCommand::SetListener(frame)) => {
+                if state.listener.replace(frame).is_some() {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Request::Command(AudioCommand::SetVoiceTransform {
+                voice,
+                position,
+                velocity,
+            }) => {
+                let transforms = &mut state.voice_transforms;
+                if transforms.len() >= self.capacity && !transforms.contains_key(&voice) {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(QueueError::Full);
+                }
+                if transforms.insert(voice, (position, velocity)).is_some() {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            request => {
+                if state.commands.len() >= self.capacity {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(QueueError::Full);
+                }
+                state.commands.push_back(QueuedCommand { request, ack });
+            }
+        }
+
+        drop(state);
+        self.ready.notify_one();
+        Ok(())
+    }
+
+    /// Waits up to `timeout` for pending commands and takes all of them. Returns `None` once the
+    /// queue has been closed.
+    pub(crate) fn pop_batch(&self, timeout: Duration) -> Option<Vec<QueuedCommand>> {
+        let mut state = self.state.lock().ok()?;
+        if !state.closed && state.is_empty() {
+            state = self.ready.wait_timeout(state, timeout).ok()?.0;
+        }
+        if state.closed {
+            return None;
+        }
+
+        let mut batch: Vec<QueuedCommand> = state.commands.drain(..).collect();
+        if let Some(frame) = state.listener.take() {
+            batch.push(AudioCommand::SetListener(frame).into());
+        }
+        let mut transforms: Vec<_> = state.voice_transforms.drain().collect();
+        transforms.sort_unstable_by_key(|(voice, _)| *voice);
+        batch.extend(transforms.into_iter().map(|(voice, (position, velocity))| {
+            AudioCommand::SetVoiceTransform {
+                voice,
+                position,
+                velocity,
+            }
+            .into()
+        }));
+        Some(batch)
+    }
+
+    /// Closes the queue and drops anything still pending, which resolves outstanding tickets
+    /// with `ThreadStopped`.
+    pub(crate) fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+            state.commands.clear();
+            state.listener = None;
+            state.voice_transforms.clear();
+        }
+        self.ready.notify_all();
+    }
+
+    pub(crate) fn stats(&self) -> QueueStats {
+        let depth = self.state.lock().map(|state| state.len()).unwrap_or(0);
+        QueueStats {
+            depth,
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl QueueState {
+    fn len(&self) -> usize {
+        self.commands.len() + usize::from(self.listener.is_some()) + self.voice_transforms.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandQueue, QueueError, Request};
+    use crate::{AudioCommand, ListenerFrame};
+    use glam::Vec3;
+    use std::time::Duration;
+
+    #[test]
+    fn coalesces_listener_and_voice_transforms() {
+        let queue = CommandQueue::new(8);
+        for x in 0..3 {
+            let frame = ListenerFrame {
+                position: Vec3::new(x as f32, 0.0, 0.0),
+                ..Default::default()
+            };
+            queue.push(AudioCommand::SetListener(frame)).unwrap();
+            queue
+                .push(AudioCommand::SetVoiceTransform {
+                    voice: 7,
+                    position: Vec3::splat(x as f32),
+                    velocity: Vec3::ZERO,
+                })
+                .unwrap();
+        }
+        queue.push(AudioCommand::StopLoop).unwrap();
+
+        let stats = queue.stats();
+        assert_eq!(stats.depth, 3);
+        assert_eq!(stats.coalesced, 4);
+
+        let batch = queue.pop_batch(Duration::ZERO).unwrap();
+        assert_eq!(batch.len(), 3);
+        assert!(matches!(
+            batch[0].request,
+            Request::Command(AudioCommand::StopLoop)
+        ));
+        assert!(matches!(
+            batch[1].request,
+            Request::Command(AudioCommand::SetListener(frame)) if frame.position.x == 2.0
+        ));
+        assert!(matches!(
+            batch[2].request,
+            Request::Command(AudioCommand::SetVoiceTransform { voice: 7, position, .. })
+                if position.x == 2.0
+        ));
+    }
+
+    #[test]
+    fn acknowledged_commands_keep_their_order() {
+        let queue = CommandQueue::new(8);
+        let frame_at = |x: f32| ListenerFrame {
+            position: Vec3::new(x, 0.0, 0.0),
+            ..Default::default()
+        };
+        let ack: super::Ack = Box::new(|_| {});
+        queue
+            .push(AudioCommand::SetListener(frame_at(1.0)))
+            .unwrap();
+        queue
+            .push_acked(
+                Request::Command(AudioCommand::SetListener(frame_at(2.0))),
+                ack,
+            )
+            .unwrap();
+
+        let batch = queue.pop_batch(Duration::ZERO).unwrap();
+        let last_listener = batch
+            .iter()
+            .rev()
+            .find(|queued| {
+                matches!(
+                    queued.request,
+                    Request::Command(AudioCommand::SetListener(_))
+                )
+            })
+            .unwrap();
+        assert!(last_listener.ack.is_some());
+        assert!(matches!(
+            last_listener.request,
+            Request::Command(AudioCommand::SetListener(frame)) if frame.position.x == 2.0
+        ));
+    }
+
+    #[test]
+    fn rejects_commands_beyond_capacity() {
+        let queue = CommandQueue::new(2);
+        queue.push(AudioCommand::StopLoop).unwrap();
+        queue.push(AudioCommand::StopLoop).unwrap();
+        assert_eq!(queue.push(AudioCommand::StopLoop), Err(QueueError::Full));
+        assert_eq!(queue.stats().dropped, 1);
+
+        queue.close();
+        assert_eq!(queue.push(AudioCommand::StopLoop), Err(QueueError::Closed));
+        assert!(queue.pop_batch(Duration::ZERO).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::prelude::*;
+use ron::ser::PrettyConfig;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::OpenalSettings;
+
+#[derive(Debug, Error)]
+pub enum OpenalConfigError {
+    #[error("failed to read audio config {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to write audio config {}: {source}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+    #[error("invalid audio config {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: Box<ron::error::SpannedError>,
+    },
+    #[error("failed to serialize audio config: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl OpenalSettings {
+    /// Reads settings from a RON file. Missing fields keep their defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OpenalConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| OpenalConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_ron(&text).map_err(|source| OpenalConfigError::Parse {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })
+    }
+
+    /// Writes the settings to `path` as pretty-printed RON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), OpenalConfigError> {
+        let path = path.as_ref();
+        let text = self.to_ron()?;
+        fs::write(path, text).map_err(|source| OpenalConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, PrettyConfig::default())
+    }
+}
+
+/// Where `BevyOpenalPlugin` keeps its [`OpenalSettings`].
+///
+/// Insert this resource before the app starts. The file is read before the runtime is created
+/// (replacing the `OpenalSettings` resource; a missing file is written with the current
+/// settings), and with `save_on_change` the settings are written back whenever they change.
+/// A file that fails to parse is left alone until the settings are changed.
+#[derive(Resource, Clone, Debug)]
+pub struct OpenalSettingsFile {
+    pub path: PathBuf,
+    pub save_on_change: bool,
+}
+
+impl OpenalSettingsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            save_on_change: true,
+        }
+    }
+}
+
+/// RON last read from or written to the settings file, so unchanged settings are not rewritten.
+#[derive(Resource, Default)]
+pub(crate) struct SavedSettings(Option<String>);
+
+pub(crate) fn load_settings_file_system(
+    file: Option<Res<OpenalSettingsFile>>,
+    mut settings: ResMut<OpenalSettings>,
+    mut saved: ResMut<SavedSettings>,
+) {
+    let Some(file) = file else {
+        return;
+    };
+    if !file.path.exists() {
+        match settings.save(&file.path) {
+            Ok(()) => info!(path = %file.path.display(), "Wrote default audio config"),
+            Err(err) => warn!(error = %err, "Failed to write default audio config"),
+        }
+        saved.0 = settings.to_ron().ok();
+        return;
+    }
+    match OpenalSettings::load(&file.path) {
+        Ok(loaded) => {
+            info!(path = %file.path.display(), "Loaded audio config");
+            saved.0 = loaded.to_ron().ok();
+            *settings = loaded;
+        }
+        Err(err) => {
+            warn!(error = %err, "Failed to load audio config; using defaults");
+            // Treat the current settings as already saved so the unreadable file is only
+            // overwritten once the settings actually change.
+            saved.0 = settings.to_ron().ok();
+        }
+    }
+}
+
+pub(crate) fn save_settings_file_system(
+    file: Option<Res<OpenalSettingsFile>>,
+    settings: Res<OpenalSettings>,
+    mut saved: ResMut<SavedSettings>,
+) {
+    let Some(file) = file.filter(|file| file.save_on_change) else {
+        return;
+    };
+    if !settings.is_changed() {
+        return;
+    }
+    let text = match settings.to_ron() {
+        Ok(text) => text,
+        Err(err) => {
+            warn!(error = %err, "Failed to serialize audio config");
+            return;
+        }
+    };
+    if saved.0.as_ref() == Some(&text) {
+        return;
+    }
+    match fs::write(&file.path, &text) {
+        Ok(()) => saved.0 = Some(text),
+        Err(err) => warn!(path = %file.path.display(), error = %err, "Failed to save audio config"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use bevy_ecs::prelude::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    use super::{
+        load_settings_file_system, save_settings_file_system, OpenalSettingsFile, SavedSettings,
+    };
+    use crate::{AudioRenderMode, BusVolumes, OpenalSettings};
+
+    #[test]
+    fn settings_round_trip_and_fill_defaults() {
+        let settings = OpenalSettings {
+            render_mode: AudioRenderMode::HeadphonesHrtf,
+            preferred_device: Some("Speakers".to_string()),
+            listener_blend: Duration::from_millis(250),
+            bus_volumes: BusVolumes {
+                music: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let text = settings.to_ron().unwrap();
+        assert_eq!(OpenalSettings::from_ron(&text).unwrap(), settings);
+
+        let partial =
+            OpenalSettings::from_ron("(max_sources: 32, bus_volumes: (ui: 0.25))").unwrap();
+        assert_eq!(partial.max_sources, 32);
+        assert_eq!(partial.render_mode, OpenalSettings::default().render_mode);
+        assert_eq!(partial.bus_volumes.ui, 0.25);
+        assert_eq!(partial.bus_volumes.music, 1.0);
+    }
+
+    #[test]
+    fn invalid_config_file_is_not_overwritten_with_defaults() {
+        let path = std::env::temp_dir().join(format!(
+            "bevy-openal-invalid-config-{}.ron",
+            std::process::id()
+        ));
+        let broken = "(max_sources: 32,, render_mode: Stereo)";
+        fs::write(&path, broken).unwrap();
+
+        let mut world = World::new();
+        world.init_resource::<OpenalSettings>();
+        world.init_resource::<SavedSettings>();
+        world.insert_resource(OpenalSettingsFile::new(&path));
+        world.run_system_once(load_settings_file_system).unwrap();
+        world.run_system_once(save_settings_file_system).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), broken);
+
+        world.resource_mut::<OpenalSettings>().max_sources = 16;
+        world.run_system_once(save_settings_file_system).unwrap();
+        let saved = OpenalSettings::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved.max_sources, 16);
+    }
+}
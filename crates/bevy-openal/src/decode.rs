@@ -1,31 +1,183 @@
-use rodio::Source;
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::Range;
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::{ImportSampleRate, SymphoniaDecoder};
+
+/// Zero crossings of the windowed-sinc kernel on each side of the interpolation point.
+const SINC_ZERO_CROSSINGS: f64 = 16.0;
+/// Low-pass cutoff as a fraction of the lower Nyquist rate, leaving room for the window's
+/// transition band.
+const SINC_CUTOFF: f64 = 0.95;
+/// Samples at or below this magnitude (about -60 dBFS) count as silence for trimming.
+const SILENCE_THRESHOLD: u16 = 33;
+
 #[derive(Debug, Clone)]
 pub struct DecodedAudioMono16 {
     pub sample_rate_hz: u32,
     pub samples: Vec<i16>,
+    /// Region, in sample frames, that looping voices repeat (`AL_SOFT_loop_points`); `None` loops
+    /// the whole buffer.
+    pub loop_frames: Option<Range<usize>>,
+}
+
+/// Conditioning applied to decoded audio before it becomes a buffer.
+///
+/// The defaults leave the audio untouched and follow the runtime's import rate. Imports are
+/// always downmixed to mono and decoded fully; there is no keep-channels or streaming option yet.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ImportOptions {
+    /// Overrides `AudioRuntimeConfig::import_sample_rate` for this buffer.
+    pub sample_rate: Option<ImportSampleRate>,
+    /// Scale the audio so its peak reaches full scale, before `gain_db` is applied.
+    pub normalize: bool,
+    /// Gain baked into the samples, in decibels.
+    pub gain_db: f32,
+    /// Drop leading and trailing silence.
+    pub trim_silence: bool,
+    /// Loop region start in seconds of the source file; `None` starts at the beginning.
+    pub loop_start: Option<f32>,
+    /// Loop region end in seconds of the source file; `None` ends at the end.
+    pub loop_end: Option<f32>,
+}
+
+impl ImportOptions {
+    /// Applies trimming, loop points and gain. Resampling is left to the caller, which knows the
+    /// import rate.
+    pub fn condition(&self, mut decoded: DecodedAudioMono16) -> DecodedAudioMono16 {
+        let mut leading = 0;
+        if self.trim_silence {
+            let loud = |sample: &i16| sample.unsigned_abs() > SILENCE_THRESHOLD;
+            let start = decoded.samples.iter().position(loud).unwrap_or(0);
+            let end = decoded
+                .samples
+                .iter()
+                .rposition(loud)
+                .map_or(0, |last| last + 1);
+            decoded.samples.truncate(end);
+            decoded.samples.drain(..start.min(end));
+            leading = start;
+        }
+
+        if self.loop_start.is_some() || self.loop_end.is_some() {
+            let len = decoded.samples.len();
+            let rate = f64::from(decoded.sample_rate_hz);
+            let frame = |seconds: f32| {
+                ((f64::from(seconds.max(0.0)) * rate).round() as usize)
+                    .saturating_sub(leading)
+                    .min(len)
+            };
+            let start = self.loop_start.map_or(0, frame);
+            let end = self.loop_end.map_or(len, frame);
+            decoded.loop_frames = (start < end).then_some(start..end);
+        }
+
+        let mut scale = 10f32.powf(self.gain_db / 20.0);
+        if self.normalize {
+            let peak = decoded
+                .samples
+                .iter()
+                .map(|sample| sample.unsigned_abs())
+                .max()
+                .unwrap_or(0);
+            if peak > 0 {
+                scale *= f32::from(i16::MAX) / f32::from(peak);
+            }
+        }
+        if scale.is_finite() && scale != 1.0 {
+            for sample in &mut decoded.samples {
+                *sample = (f32::from(*sample) * scale)
+                    .round()
+                    .clamp(f32::from(i16::MIN), f32::from(i16::MAX))
+                    as i16;
+            }
+        }
+
+        decoded
+    }
+}
+
+/// Properties of the encoded source, reported alongside the mono PCM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedAudioInfo {
+    /// Short codec name, e.g. `vorbis`, `flac` or `pcm_s16le`.
+    pub codec: String,
+    /// Channel count before the mono downmix.
+    pub channels: usize,
+    pub sample_rate_hz: u32,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    pub pcm: DecodedAudioMono16,
+    pub info: DecodedAudioInfo,
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum DecodeError {
-    #[error("audio decode failed: {0}")]
-    Decoder(#[from] rodio::decoder::DecoderError),
+    #[error("unrecognized or disabled container format: {0}")]
+    UnsupportedFormat(String),
+    #[error("no decodable audio track")]
+    NoAudioTrack,
+    #[error("codec `{codec}` is not enabled in this build")]
+    CodecDisabled { codec: String },
+    #[error("unsupported {codec} stream: {reason}")]
+    Unsupported { codec: String, reason: String },
+    #[error("malformed {codec} stream: {reason}")]
+    Malformed { codec: String, reason: String },
+    #[error("{codec} decoder limit reached: {reason}")]
+    LimitReached { codec: String, reason: String },
+    #[error("audio read failed: {0}")]
+    Io(String),
+    #[error("{codec} stream does not declare a sample rate")]
+    MissingSampleRate { codec: String },
 }
 
-pub fn decode_to_mono_i16(bytes: &[u8]) -> Result<DecodedAudioMono16, DecodeError> {
-    let cursor = std::io::Cursor::new(bytes.to_vec());
-    let decoder = rodio::Decoder::new(cursor)?;
-    let sample_rate_hz = decoder.sample_rate();
-    let channels = decoder.channels() as usize;
+/// Turns a complete encoded file into mono 16-bit PCM.
+///
+/// Implementations run on the decode worker threads, so they must be shareable.
+pub trait AudioDecoder: Send + Sync + fmt::Debug {
+    fn decode(&self, bytes: &[u8]) -> Result<DecodedAudio, DecodeError>;
+}
 
-    let samples: Vec<i16> = decoder.convert_samples::<i16>().collect();
-    let samples = downmix_to_mono_i16(samples, channels);
+/// Decodes with the default [`SymphoniaDecoder`] and drops the source info.
+pub fn decode_to_mono_i16(bytes: &[u8]) -> Result<DecodedAudioMono16, DecodeError> {
+    SymphoniaDecoder.decode(bytes).map(|decoded| decoded.pcm)
+}
 
-    Ok(DecodedAudioMono16 {
-        sample_rate_hz,
-        samples,
-    })
+impl DecodedAudio {
+    /// Downmixes interleaved PCM and fills in the derived info.
+    pub(crate) fn from_interleaved(
+        codec: String,
+        channels: usize,
+        sample_rate_hz: u32,
+        interleaved: Vec<i16>,
+    ) -> Self {
+        let channels = channels.max(1);
+        let samples = downmix_to_mono_i16(interleaved, channels);
+        let duration = Duration::from_secs_f64(samples.len() as f64 / f64::from(sample_rate_hz));
+        Self {
+            pcm: DecodedAudioMono16 {
+                sample_rate_hz,
+                samples,
+                loop_frames: None,
+            },
+            info: DecodedAudioInfo {
+                codec,
+                channels,
+                sample_rate_hz,
+                duration,
+            },
+        }
+    }
 }
 
 fn downmix_to_mono_i16(samples: Vec<i16>, channels: usize) -> Vec<i16> {
@@ -44,3 +196,139 @@ fn downmix_to_mono_i16(samples: Vec<i16>, channels: usize) -> Vec<i16> {
 
     mono
 }
+
+/// Converts `decoded` to `target_rate_hz` with a Blackman-windowed sinc filter.
+///
+/// Intended for import time (worker or loader threads): the kernel is long enough to keep aliasing
+/// well below 16-bit noise, which is far too slow for the realtime audio thread. Returns a copy
+/// unchanged when the rates already match.
+pub fn resample_mono_i16(decoded: &DecodedAudioMono16, target_rate_hz: u32) -> DecodedAudioMono16 {
+    let source_rate_hz = decoded.sample_rate_hz;
+    let samples = &decoded.samples;
+    if target_rate_hz == 0
+        || source_rate_hz == 0
+        || source_rate_hz == target_rate_hz
+        || samples.is_empty()
+    {
+        return decoded.clone();
+    }
+
+    let ratio = f64::from(target_rate_hz) / f64::from(source_rate_hz);
+    let cutoff = ratio.min(1.0) * SINC_CUTOFF;
+    let half_width = SINC_ZERO_CROSSINGS / cutoff;
+    let last_index = samples.len() - 1;
+    let output_len = (samples.len() as f64 * ratio).round() as usize;
+
+    let mut output = Vec::with_capacity(output_len);
+    for n in 0..output_len {
+        let center = n as f64 / ratio;
+        let first = (center - half_width).ceil().max(0.0) as usize;
+        let last = ((center + half_width).floor() as usize).min(last_index);
+
+        let mut acc = 0.0;
+        for (offset, &sample) in samples[first..=last].iter().enumerate() {
+            let t = (first + offset) as f64 - center;
+            let weight = cutoff * sinc(cutoff * t) * blackman(t / half_width);
+            acc += f64::from(sample) * weight;
+        }
+        output.push(acc.round().clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16);
+    }
+
+    let scale_frame = |frame: usize| ((frame as f64 * ratio).round() as usize).min(output.len());
+    let loop_frames = decoded
+        .loop_frames
+        .as_ref()
+        .map(|frames| scale_frame(frames.start)..scale_frame(frames.end))
+        .filter(|frames| !frames.is_empty());
+
+    DecodedAudioMono16 {
+        sample_rate_hz: target_rate_hz,
+        samples: output,
+        loop_frames,
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        let px = PI * x;
+        px.sin() / px
+    }
+}
+
+/// Blackman window over `x` in `[-1, 1]`.
+fn blackman(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        return 0.0;
+    }
+    0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resample_mono_i16, DecodedAudioMono16, ImportOptions};
+
+    fn sine(rate_hz: u32, freq_hz: f64, seconds: f64) -> Vec<i16> {
+        let len = (f64::from(rate_hz) * seconds) as usize;
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / f64::from(rate_hz);
+                ((2.0 * std::f64::consts::PI * freq_hz * t).sin() * 16_000.0) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resamples_sine_to_target_rate() {
+        let input = DecodedAudioMono16 {
+            sample_rate_hz: 44_100,
+            samples: sine(44_100, 1_000.0, 0.5),
+            loop_frames: Some(4_410..22_050),
+        };
+        let output = resample_mono_i16(&input, 48_000);
+        assert_eq!(output.sample_rate_hz, 48_000);
+        assert_eq!(output.samples.len(), 24_000);
+        assert_eq!(output.loop_frames, Some(4_800..24_000));
+
+        // Skip the edges, where the kernel runs off the end of the input.
+        let expected = sine(48_000, 1_000.0, 0.5);
+        let max_error = output.samples[200..23_800]
+            .iter()
+            .zip(&expected[200..23_800])
+            .map(|(a, b)| (i32::from(*a) - i32::from(*b)).abs())
+            .max()
+            .unwrap();
+        assert!(max_error < 100, "max error {max_error}");
+    }
+
+    #[test]
+    fn matching_rate_is_unchanged() {
+        let input = DecodedAudioMono16 {
+            sample_rate_hz: 48_000,
+            samples: vec![1, 2, 3],
+            loop_frames: None,
+        };
+        assert_eq!(resample_mono_i16(&input, 48_000).samples, input.samples);
+    }
+
+    #[test]
+    fn condition_trims_normalizes_and_places_loop() {
+        let input = DecodedAudioMono16 {
+            sample_rate_hz: 10,
+            samples: vec![0, 5, 120, -200, 50, 0, 0],
+            loop_frames: None,
+        };
+        let options = ImportOptions {
+            normalize: true,
+            trim_silence: true,
+            loop_start: Some(0.3),
+            loop_end: Some(0.9),
+            ..Default::default()
+        };
+        let output = options.condition(input);
+        assert_eq!(output.samples, vec![19_660, -32_767, 8_192]);
+        // Loop points are given in source time, so they move with the trimmed lead-in.
+        assert_eq!(output.loop_frames, Some(1..3));
+    }
+}
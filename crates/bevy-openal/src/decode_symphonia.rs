@@ -0,0 +1,388 @@
+use std::io::{Cursor, ErrorKind};
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{
+    CodecType, DecoderOptions, CODEC_TYPE_FLAC, CODEC_TYPE_MP3, CODEC_TYPE_NULL, CODEC_TYPE_OPUS,
+    CODEC_TYPE_VORBIS,
+};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, Packet};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::debug;
+
+use crate::decode::{AudioDecoder, DecodeError, DecodedAudio};
+
+/// Default decoder backed by symphonia.
+///
+/// Only the containers and codecs enabled through this crate's `wav`, `vorbis`, `flac` and `mp3`
+/// features are registered. Ogg Opus is demuxed by symphonia and decoded with libopus when the
+/// `opus` feature is enabled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SymphoniaDecoder;
+
+impl AudioDecoder for SymphoniaDecoder {
+    fn decode(&self, bytes: &[u8]) -> Result<DecodedAudio, DecodeError> {
+        let source =
+            MediaSourceStream::new(Box::new(Cursor::new(bytes.to_vec())), Default::default());
+        let probed = symphonia::default::get_probe()
+            .format(
+                &Hint::new(),
+                source,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .map_err(|err| match err {
+                SymphoniaError::Unsupported(reason) => {
+                    DecodeError::UnsupportedFormat(reason.to_string())
+                }
+                err => map_error("container", err),
+            })?;
+        let mut format = probed.format;
+
+        let track = format
+            .tracks()
+            .iter()
+            .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or(DecodeError::NoAudioTrack)?;
+        let track_id = track.id;
+        let params = track.codec_params.clone();
+        let codec = codec_name(params.codec);
+
+        if params.codec == CODEC_TYPE_OPUS {
+            #[cfg(feature = "opus")]
+            return opus::decode(format.as_mut(), track_id, &params);
+            #[cfg(not(feature = "opus"))]
+            return Err(DecodeError::CodecDisabled { codec });
+        }
+
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&params, &DecoderOptions::default())
+            .map_err(|err| match err {
+                SymphoniaError::Unsupported(_) => DecodeError::CodecDisabled {
+                    codec: codec.clone(),
+                },
+                err => map_error(&codec, err),
+            })?;
+
+        let mut channels = params.channels.map(|channels| channels.count());
+        let mut sample_rate_hz = params.sample_rate;
+        let mut interleaved: Vec<i16> = Vec::new();
+        let mut buffer: Option<SampleBuffer<i16>> = None;
+
+        while let Some(packet) = next_packet(format.as_mut(), track_id, &codec)? {
+            let audio = match decoder.decode(&packet) {
+                Ok(audio) => audio,
+                // A corrupt packet is skipped; the rest of the stream is still usable.
+                Err(SymphoniaError::DecodeError(reason)) => {
+                    debug!(codec = %codec, reason, "Skipping undecodable audio packet");
+                    continue;
+                }
+                Err(err) => return Err(map_error(&codec, err)),
+            };
+            let spec = *audio.spec();
+            channels.get_or_insert(spec.channels.count());
+            sample_rate_hz.get_or_insert(spec.rate);
+
+            let needed = audio.capacity() * spec.channels.count();
+            let buffer = match &mut buffer {
+                Some(buffer) if buffer.capacity() >= needed => buffer,
+                slot => slot.insert(SampleBuffer::new(audio.capacity() as u64, spec)),
+            };
+            buffer.copy_interleaved_ref(audio);
+            interleaved.extend_from_slice(buffer.samples());
+        }
+
+        let sample_rate_hz = sample_rate_hz.filter(|&rate| rate > 0).ok_or_else(|| {
+            DecodeError::MissingSampleRate {
+                codec: codec.clone(),
+            }
+        })?;
+        Ok(DecodedAudio::from_interleaved(
+            codec,
+            channels.unwrap_or(1),
+            sample_rate_hz,
+            interleaved,
+        ))
+    }
+}
+
+/// Returns the next packet of `track_id`, or `None` at the end of the stream.
+fn next_packet(
+    format: &mut dyn FormatReader,
+    track_id: u32,
+    codec: &str,
+) -> Result<Option<Packet>, DecodeError> {
+    loop {
+        match format.next_packet() {
+            Ok(packet) if packet.track_id() == track_id => return Ok(Some(packet)),
+            Ok(_) => continue,
+            Err(SymphoniaError::IoError(err)) if err.kind() == ErrorKind::UnexpectedEof => {
+                return Ok(None);
+            }
+            // Chained streams (e.g. concatenated Ogg) are not followed.
+            Err(SymphoniaError::ResetRequired) => return Ok(None),
+            Err(err) => return Err(map_error(codec, err)),
+        }
+    }
+}
+
+fn codec_name(codec: CodecType) -> String {
+    if let Some(descriptor) = symphonia::default::get_codecs().get_codec(codec) {
+        return descriptor.short_name.to_string();
+    }
+    // Codecs whose decoder is compiled out are not in the registry.
+    match codec {
+        CODEC_TYPE_VORBIS => "vorbis".to_string(),
+        CODEC_TYPE_FLAC => "flac".to_string(),
+        CODEC_TYPE_MP3 => "mp3".to_string(),
+        CODEC_TYPE_OPUS => "opus".to_string(),
+        codec => format!("codec {codec}"),
+    }
+}
+
+fn map_error(codec: &str, err: SymphoniaError) -> DecodeError {
+    let codec = codec.to_string();
+    match err {
+        SymphoniaError::IoError(err) => DecodeError::Io(err.to_string()),
+        SymphoniaError::DecodeError(reason) => DecodeError::Malformed {
+            codec,
+            reason: reason.to_string(),
+        },
+        SymphoniaError::Unsupported(reason) => DecodeError::Unsupported {
+            codec,
+            reason: reason.to_string(),
+        },
+        SymphoniaError::LimitError(reason) => DecodeError::LimitReached {
+            codec,
+            reason: reason.to_string(),
+        },
+        err @ (SymphoniaError::SeekError(_) | SymphoniaError::ResetRequired) => {
+            DecodeError::Malformed {
+                codec,
+                reason: err.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "opus")]
+mod opus {
+    use audiopus::coder::Decoder;
+    use audiopus::{Channels, SampleRate};
+    use symphonia::core::codecs::CodecParameters;
+    use symphonia::core::formats::FormatReader;
+    use tracing::debug;
+
+    use super::next_packet;
+    use crate::decode::{DecodeError, DecodedAudio};
+
+    const CODEC: &str = "opus";
+    /// Ogg Opus always decodes at 48 kHz.
+    const OPUS_RATE_HZ: u32 = 48_000;
+    /// Longest Opus frame (120 ms at 48 kHz), per channel.
+    const MAX_FRAME_SAMPLES: usize = 5_760;
+
+    pub(super) fn decode(
+        format: &mut dyn FormatReader,
+        track_id: u32,
+        params: &CodecParameters,
+    ) -> Result<DecodedAudio, DecodeError> {
+        let channel_count = params.channels.map_or(0, |channels| channels.count());
+        let channels = match channel_count {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            count => {
+                return Err(DecodeError::Unsupported {
+                    codec: CODEC.to_string(),
+                    reason: format!("{count} channels (multistream Opus is not supported)"),
+                })
+            }
+        };
+        let mut decoder = Decoder::new(SampleRate::Hz48000, channels).map_err(opus_error)?;
+
+        let mut frame = vec![0i16; MAX_FRAME_SAMPLES * channel_count];
+        let mut interleaved: Vec<i16> = Vec::new();
+        while let Some(packet) = next_packet(format, track_id, CODEC)? {
+            let decoded = match decoder.decode(Some(&packet.data[..]), &mut frame[..], false) {
+                Ok(decoded) => decoded,
+                // Like the symphonia path, a corrupt packet is skipped.
+                Err(err) => {
+                    debug!(codec = CODEC, reason = %err, "Skipping undecodable audio packet");
+                    continue;
+                }
+            };
+            interleaved.extend_from_slice(&frame[..decoded * channel_count]);
+        }
+
+        // Pre-skip is encoder priming, not program audio.
+        let pre_skip = params.delay.unwrap_or(0) as usize * channel_count;
+        interleaved.drain(..pre_skip.min(interleaved.len()));
+
+        Ok(DecodedAudio::from_interleaved(
+            CODEC.to_string(),
+            channel_count,
+            OPUS_RATE_HZ,
+            interleaved,
+        ))
+    }
+
+    fn opus_error(err: audiopus::Error) -> DecodeError {
+        DecodeError::Malformed {
+            codec: CODEC.to_string(),
+            reason: err.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use audiopus::coder::Encoder;
+        use audiopus::{Application, Channels, SampleRate};
+
+        use crate::decode::AudioDecoder;
+        use crate::SymphoniaDecoder;
+
+        const PRE_SKIP: u16 = 312;
+        /// 20 ms at 48 kHz.
+        const FRAME: usize = 960;
+
+        /// Ogg CRC-32: polynomial 0x04c11db7, no reflection, zero initial value.
+        fn ogg_crc(bytes: &[u8]) -> u32 {
+            bytes.iter().fold(0u32, |crc, &byte| {
+                (0..8).fold(crc ^ (u32::from(byte) << 24), |crc, _| {
+                    if crc & 0x8000_0000 != 0 {
+                        (crc << 1) ^ 0x04c1_1db7
+                    } else {
+                        crc << 1
+                    }
+                })
+            })
+        }
+
+        /// One packet per page.
+        fn ogg_page(out: &mut Vec<u8>, flags: u8, granule: u64, sequence: u32, packet: &[u8]) {
+            let start = out.len();
+            out.extend_from_slice(b"OggS");
+            out.push(0);
+            out.push(flags);
+            out.extend_from_slice(&granule.to_le_bytes());
+            out.extend_from_slice(&1u32.to_le_bytes());
+            out.extend_from_slice(&sequence.to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes());
+            let mut lacing = vec![255u8; packet.len() / 255];
+            lacing.push((packet.len() % 255) as u8);
+            out.push(lacing.len() as u8);
+            out.extend_from_slice(&lacing);
+            out.extend_from_slice(packet);
+            let crc = ogg_crc(&out[start..]);
+            out[start + 22..start + 26].copy_from_slice(&crc.to_le_bytes());
+        }
+
+        /// Mono Ogg Opus stream of `packets`, each decoding to one 20 ms frame.
+        fn ogg_opus(packets: &[Vec<u8>]) -> Vec<u8> {
+            let mut head = b"OpusHead".to_vec();
+            head.push(1);
+            head.push(1);
+            head.extend_from_slice(&PRE_SKIP.to_le_bytes());
+            head.extend_from_slice(&48_000u32.to_le_bytes());
+            head.extend_from_slice(&0i16.to_le_bytes());
+            head.push(0);
+            let mut tags = b"OpusTags".to_vec();
+            tags.extend_from_slice(&4u32.to_le_bytes());
+            tags.extend_from_slice(b"test");
+            tags.extend_from_slice(&0u32.to_le_bytes());
+
+            let mut out = Vec::new();
+            ogg_page(&mut out, 0x02, 0, 0, &head);
+            ogg_page(&mut out, 0x00, 0, 1, &tags);
+            for (index, packet) in packets.iter().enumerate() {
+                let last = index + 1 == packets.len();
+                let granule = ((index + 1) * FRAME) as u64;
+                let flags = if last { 0x04 } else { 0x00 };
+                ogg_page(&mut out, flags, granule, index as u32 + 2, packet);
+            }
+            out
+        }
+
+        #[test]
+        fn decodes_ogg_opus_and_skips_corrupt_packets() {
+            let encoder =
+                Encoder::new(SampleRate::Hz48000, Channels::Mono, Application::Audio).unwrap();
+            let tone: Vec<i16> = (0..FRAME)
+                .map(|i| ((i as f32 * 0.05).sin() * 8_000.0) as i16)
+                .collect();
+            let mut packets: Vec<Vec<u8>> = (0..5)
+                .map(|_| {
+                    let mut packet = vec![0u8; 1_500];
+                    let len = encoder.encode(&tone, &mut packet).unwrap();
+                    packet.truncate(len);
+                    packet
+                })
+                .collect();
+            // Code 3 TOC with a zero frame count is an invalid packet.
+            packets.insert(2, vec![0x03, 0x00]);
+
+            let decoded = SymphoniaDecoder.decode(&ogg_opus(&packets)).unwrap();
+            assert_eq!(decoded.info.codec, "opus");
+            assert_eq!(decoded.info.sample_rate_hz, 48_000);
+            assert_eq!(decoded.pcm.samples.len(), 5 * FRAME - usize::from(PRE_SKIP));
+            assert!(decoded
+                .pcm
+                .samples
+                .iter()
+                .any(|&sample| sample.abs() > 1_000));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "wav"))]
+mod tests {
+    use super::SymphoniaDecoder;
+    use crate::decode::{AudioDecoder, DecodeError};
+
+    fn stereo_wav(frames: &[(i16, i16)], sample_rate_hz: u32) -> Vec<u8> {
+        let data_len = (frames.len() * 4) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate_hz.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate_hz * 4).to_le_bytes());
+        bytes.extend_from_slice(&4u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for (left, right) in frames {
+            bytes.extend_from_slice(&left.to_le_bytes());
+            bytes.extend_from_slice(&right.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn decodes_wav_with_info_and_downmix() {
+        let frames = vec![(1000, 3000); 4_410];
+        let decoded = SymphoniaDecoder
+            .decode(&stereo_wav(&frames, 44_100))
+            .unwrap();
+
+        assert_eq!(decoded.info.codec, "pcm_s16le");
+        assert_eq!(decoded.info.channels, 2);
+        assert_eq!(decoded.info.sample_rate_hz, 44_100);
+        assert_eq!(decoded.info.duration.as_millis(), 100);
+        assert_eq!(decoded.pcm.samples.len(), 4_410);
+        assert!(decoded.pcm.samples.iter().all(|&sample| sample == 2000));
+    }
+
+    #[test]
+    fn rejects_unknown_container() {
+        let err = SymphoniaDecoder
+            .decode(b"definitely not audio")
+            .unwrap_err();
+        assert!(matches!(err, DecodeError::UnsupportedFormat(_)), "{err}");
+    }
+}
@@ -0,0 +1,257 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use tracing::{debug, warn};
+
+use crate::runtime::send_event;
+use crate::{
+    resample_mono_i16, AudioCommand, AudioDecoder, AudioEvent, AudioHandle, AudioRuntimeStatus,
+    BufferKey, DecodedAudioMono16, ImportOptions, ImportSampleRate, RuntimeError,
+};
+
+/// Audio handed to the pool: an encoded file, or PCM that only needs conditioning.
+pub(crate) enum ImportSource {
+    Encoded(Vec<u8>),
+    Pcm(DecodedAudioMono16),
+}
+
+struct DecodeJob {
+    key: BufferKey,
+    source: ImportSource,
+    /// Replace an existing buffer instead of creating a new one.
+    replace: bool,
+    options: ImportOptions,
+}
+
+/// Small pool of threads that decode encoded audio, condition and resample it to the import rate,
+/// and upload the result as a buffer.
+///
+/// Decoding never runs on the realtime audio thread or the caller's thread. Each job ends with
+/// exactly one `BufferReady`/`BufferFailed` event; progress counters live in the runtime status.
+pub(crate) struct DecodePool {
+    jobs: Option<mpsc::Sender<DecodeJob>>,
+    workers: Vec<thread::JoinHandle<()>>,
+    stopping: Arc<AtomicBool>,
+    status: Arc<Mutex<AudioRuntimeStatus>>,
+}
+
+impl DecodePool {
+    pub(crate) fn new(
+        worker_count: usize,
+        decoder: Arc<dyn AudioDecoder>,
+        import_sample_rate: ImportSampleRate,
+        handle: AudioHandle,
+        events: mpsc::SyncSender<AudioEvent>,
+        status: Arc<Mutex<AudioRuntimeStatus>>,
+    ) -> Self {
+        let (jobs, receiver) = mpsc::channel::<DecodeJob>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let stopping = Arc::new(AtomicBool::new(false));
+
+        let workers = (0..worker_count.max(1))
+            .filter_map(|index| {
+                let receiver = Arc::clone(&receiver);
+                let handle = handle.clone();
+                let decoder = Arc::clone(&decoder);
+                let events = events.clone();
+                let status = Arc::clone(&status);
+                let stopping = Arc::clone(&stopping);
+                thread::Builder::new()
+                    .name(format!("zrg-audio-decode-{index}"))
+                    .spawn(move || {
+                        let worker = Worker {
+                            decoder,
+                            import_sample_rate,
+                            handle,
+                            events,
+                            status,
+                            stopping,
+                        };
+                        worker.run(&receiver);
+                    })
+                    .map_err(|err| warn!(error = %err, "Failed to spawn audio decode worker"))
+                    .ok()
+            })
+            .collect();
+
+        Self {
+            jobs: Some(jobs),
+            workers,
+            stopping,
+            status,
+        }
+    }
+
+    pub(crate) fn submit(
+        &self,
+        key: BufferKey,
+        source: ImportSource,
+        replace: bool,
+        options: ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        let Some(jobs) = self.jobs.as_ref().filter(|_| !self.workers.is_empty()) else {
+            return Err(RuntimeError::NotAvailable);
+        };
+        if let Ok(mut st) = self.status.lock() {
+            st.decodes_pending += 1;
+        }
+        jobs.send(DecodeJob {
+            key,
+            source,
+            replace,
+            options,
+        })
+        .map_err(|_| {
+            if let Ok(mut st) = self.status.lock() {
+                st.decodes_pending = st.decodes_pending.saturating_sub(1);
+            }
+            RuntimeError::ThreadStopped
+        })
+    }
+
+    /// Abandons queued jobs (each reported as a failed decode) and waits for in-flight decodes to
+    /// finish.
+    pub(crate) fn shutdown(&mut self) {
+        self.stopping.store(true, Ordering::Relaxed);
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for DecodePool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+struct Worker {
+    decoder: Arc<dyn AudioDecoder>,
+    import_sample_rate: ImportSampleRate,
+    handle: AudioHandle,
+    events: mpsc::SyncSender<AudioEvent>,
+    status: Arc<Mutex<AudioRuntimeStatus>>,
+    stopping: Arc<AtomicBool>,
+}
+
+impl Worker {
+    fn run(&self, receiver: &Mutex<mpsc::Receiver<DecodeJob>>) {
+        loop {
+            let job = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return,
+            };
+            let Ok(DecodeJob {
+                key,
+                source,
+                replace,
+                options,
+            }) = job
+            else {
+                return;
+            };
+            // Keep draining after shutdown so every queued job still gets its event.
+            if self.stopping.load(Ordering::Relaxed) {
+                self.abandon(key);
+                continue;
+            }
+
+            let result = self.decode_and_upload(key, source, replace, &options);
+            self.finish(key, result);
+        }
+    }
+
+    fn decode_and_upload(
+        &self,
+        key: BufferKey,
+        source: ImportSource,
+        replace: bool,
+        options: &ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        let pcm = match source {
+            ImportSource::Encoded(bytes) => {
+                let decoded =
+                    self.decoder
+                        .decode(&bytes)
+                        .map_err(|err| RuntimeError::DecodeFailed {
+                            key,
+                            reason: err.to_string(),
+                        })?;
+                debug!(
+                    key,
+                    codec = %decoded.info.codec,
+                    channels = decoded.info.channels,
+                    sample_rate_hz = decoded.info.sample_rate_hz,
+                    duration_ms = decoded.info.duration.as_millis() as u64,
+                    "Decoded audio"
+                );
+                decoded.pcm
+            }
+            ImportSource::Pcm(pcm) => pcm,
+        };
+        let decoded = options.condition(pcm);
+        let policy = options.sample_rate.unwrap_or(self.import_sample_rate);
+        let target = self
+            .status
+            .lock()
+            .ok()
+            .and_then(|st| st.import_rate_hz(policy));
+        let decoded = match target {
+            Some(rate) if rate != decoded.sample_rate_hz => resample_mono_i16(&decoded, rate),
+            _ => decoded,
+        };
+        let command = if replace {
+            AudioCommand::ReplaceBuffer { key, decoded }
+        } else {
+            AudioCommand::CreateBuffer { key, decoded }
+        };
+        self.handle.submit_acked(command)?.wait()
+    }
+
+    fn abandon(&self, key: BufferKey) {
+        if let Ok(mut st) = self.status.lock() {
+            st.decodes_pending = st.decodes_pending.saturating_sub(1);
+            st.decodes_failed += 1;
+        }
+        debug!(key, "Abandoned queued audio decode at shutdown");
+        send_event(
+            &self.events,
+            &self.status,
+            AudioEvent::BufferFailed {
+                key,
+                reason: "decode abandoned at shutdown".to_string(),
+            },
+        );
+    }
+
+    fn finish(&self, key: BufferKey, result: Result<(), RuntimeError>) {
+        let Ok(mut st) = self.status.lock() else {
+            return;
+        };
+        st.decodes_pending = st.decodes_pending.saturating_sub(1);
+        match result {
+            Ok(()) => {
+                st.decodes_completed += 1;
+                drop(st);
+                debug!(key, "Created decoded audio buffer");
+                send_event(&self.events, &self.status, AudioEvent::BufferReady { key });
+            }
+            Err(err) => {
+                st.decodes_failed += 1;
+                st.record_error(err.clone());
+                drop(st);
+                warn!(key, error = %err, "Background audio decode failed");
+                send_event(
+                    &self.events,
+                    &self.status,
+                    AudioEvent::BufferFailed {
+                        key,
+                        reason: err.to_string(),
+                    },
+                );
+            }
+        }
+    }
+}
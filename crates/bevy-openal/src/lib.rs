@@ -1,18 +1,40 @@
 #[cfg(feature = "bevy-assets")]
 mod bevy_assets;
 mod bevy_plugin;
+mod command_queue;
+#[cfg(feature = "config")]
+mod config;
 mod decode;
+mod decode_symphonia;
+mod decode_worker;
 mod openal;
 mod runtime;
+mod sound_registry;
 
 #[cfg(feature = "bevy-assets")]
-pub use bevy_assets::{BevyOpenalAssetsPlugin, OpenalAudioBytes, OpenalAudioBytesLoader};
+pub use bevy_assets::{
+    AudioBufferState, BevyOpenalAssetsPlugin, OpenalAudioBuffers, OpenalAudioBytes,
+    OpenalAudioBytesLoader, OpenalDecodedAudio, OpenalDecodedAudioLoader,
+    OpenalDecodedAudioLoaderError, ASSET_BUFFER_KEY_BASE,
+};
 pub use bevy_plugin::{
-    BevyOpenalPlugin, OpenalListener, OpenalPlayOneShot, OpenalRuntime, OpenalSettings,
-    OpenalStatus,
+    BevyOpenalPlugin, OpenalAudioEvent, OpenalEmitter, OpenalEmitterControl, OpenalEmitterVoice,
+    OpenalListener, OpenalPlayOneShot, OpenalRuntime, OpenalSetting, OpenalSettings,
+    OpenalSettingsEvent, OpenalSound, OpenalSoundFailed, OpenalStatus, OpenalVelocity,
+};
+#[cfg(feature = "config")]
+pub use config::{OpenalConfigError, OpenalSettingsFile};
+pub use decode::{
+    decode_to_mono_i16, resample_mono_i16, AudioDecoder, DecodeError, DecodedAudio,
+    DecodedAudioInfo, DecodedAudioMono16, ImportOptions,
 };
-pub use decode::{decode_to_mono_i16, DecodeError, DecodedAudioMono16};
+pub use decode_symphonia::SymphoniaDecoder;
+pub use openal::{AlErrorCode, AlcErrorCode, OpenalError};
 pub use runtime::{
-    AudioRenderMode, AudioRuntime, AudioRuntimeConfig, AudioRuntimeStatus, BufferKey,
-    DistanceModel, ListenerFrame, PlayOneShotParams, RuntimeError,
+    db_from_gain, gain_from_db, AudioBus, AudioCommand, AudioErrorRecord, AudioEvent, AudioHandle,
+    AudioRenderMode, AudioRuntime, AudioRuntimeConfig, AudioRuntimeStatus, AudioTicket,
+    AudioVoiceSnapshot, BufferKey, BusVolumes, DistanceModel, ImportSampleRate, ListenerFrame,
+    OutputLayout, PlayOneShotParams, PlaybackOffset, RuntimeError, VoiceFade, VoiceFilter, VoiceId,
+    VoiceOffset, VoiceSnapshot, VoiceState,
 };
+pub use sound_registry::{SoundRegistry, SoundRegistryError};
@@ -2,18 +2,24 @@ use glam::Vec3;
 use libloading::Library;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
+use std::ops::Range;
 use std::os::raw::{c_char, c_int, c_void};
 use std::ptr::{self, NonNull};
 use std::sync::Once;
+use std::time::{Duration, Instant};
 use std::{fs, path::PathBuf};
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
 use crate::{
-    AudioRenderMode, BufferKey, DecodedAudioMono16, DistanceModel, ListenerFrame, PlayOneShotParams,
+    AudioBus, AudioEvent, AudioRenderMode, AudioVoiceSnapshot, BufferKey, BusVolumes,
+    DecodedAudioMono16, DistanceModel, ListenerFrame, OutputLayout, PlayOneShotParams,
+    PlaybackOffset, VoiceFade, VoiceFilter, VoiceId, VoiceOffset, VoiceSnapshot, VoiceState,
 };
 
 pub type ALboolean = i8;
+pub type ALchar = c_char;
+pub type ALdouble = f64;
 pub type ALenum = c_int;
 pub type ALfloat = f32;
 pub type ALint = c_int;
@@ -56,10 +62,108 @@ const AL_PITCH: ALenum = 0x1003;
 const AL_BUFFER: ALenum = 0x1009;
 const AL_SOURCE_STATE: ALenum = 0x1010;
 const AL_STOPPED: ALenum = 0x1014;
+const AL_SEC_OFFSET: ALenum = 0x1024;
+const AL_SAMPLE_OFFSET: ALenum = 0x1025;
+
+// AL_SOFT_source_latency
+const AL_SEC_OFFSET_LATENCY_SOFT: ALenum = 0x1201;
+// AL_SOFT_loop_points
+const AL_LOOP_POINTS_SOFT: ALenum = 0x2015;
 
 const AL_FORMAT_MONO16: ALenum = 0x1101;
 
-#[derive(Debug, Error)]
+const ALC_FREQUENCY: ALCenum = 0x1007;
+const ALC_MONO_SOURCES: ALCenum = 0x1010;
+const ALC_STEREO_SOURCES: ALCenum = 0x1011;
+const ALC_CONNECTED: ALCenum = 0x313;
+
+const AL_INVALID_NAME: ALenum = 0xA001;
+const AL_INVALID_ENUM: ALenum = 0xA002;
+const AL_INVALID_VALUE: ALenum = 0xA003;
+const AL_INVALID_OPERATION: ALenum = 0xA004;
+const AL_OUT_OF_MEMORY: ALenum = 0xA005;
+
+const ALC_INVALID_DEVICE: ALCenum = 0xA001;
+const ALC_INVALID_CONTEXT: ALCenum = 0xA002;
+const ALC_INVALID_ENUM: ALCenum = 0xA003;
+const ALC_INVALID_VALUE: ALCenum = 0xA004;
+const ALC_OUT_OF_MEMORY: ALCenum = 0xA005;
+
+/// Error code reported by `alGetError`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AlErrorCode {
+    InvalidName,
+    InvalidEnum,
+    InvalidValue,
+    InvalidOperation,
+    OutOfMemory,
+    Unknown(i32),
+}
+
+impl AlErrorCode {
+    fn from_raw(value: ALenum) -> Self {
+        match value {
+            AL_INVALID_NAME => AlErrorCode::InvalidName,
+            AL_INVALID_ENUM => AlErrorCode::InvalidEnum,
+            AL_INVALID_VALUE => AlErrorCode::InvalidValue,
+            AL_INVALID_OPERATION => AlErrorCode::InvalidOperation,
+            AL_OUT_OF_MEMORY => AlErrorCode::OutOfMemory,
+            other => AlErrorCode::Unknown(other),
+        }
+    }
+}
+
+impl std::fmt::Display for AlErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlErrorCode::InvalidName => f.write_str("AL_INVALID_NAME"),
+            AlErrorCode::InvalidEnum => f.write_str("AL_INVALID_ENUM"),
+            AlErrorCode::InvalidValue => f.write_str("AL_INVALID_VALUE"),
+            AlErrorCode::InvalidOperation => f.write_str("AL_INVALID_OPERATION"),
+            AlErrorCode::OutOfMemory => f.write_str("AL_OUT_OF_MEMORY"),
+            AlErrorCode::Unknown(value) => write!(f, "AL error 0x{value:04X}"),
+        }
+    }
+}
+
+/// Error code reported by `alcGetError`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AlcErrorCode {
+    InvalidDevice,
+    InvalidContext,
+    InvalidEnum,
+    InvalidValue,
+    OutOfMemory,
+    Unknown(i32),
+}
+
+impl AlcErrorCode {
+    fn from_raw(value: ALCenum) -> Self {
+        match value {
+            ALC_INVALID_DEVICE => AlcErrorCode::InvalidDevice,
+            ALC_INVALID_CONTEXT => AlcErrorCode::InvalidContext,
+            ALC_INVALID_ENUM => AlcErrorCode::InvalidEnum,
+            ALC_INVALID_VALUE => AlcErrorCode::InvalidValue,
+            ALC_OUT_OF_MEMORY => AlcErrorCode::OutOfMemory,
+            other => AlcErrorCode::Unknown(other),
+        }
+    }
+}
+
+impl std::fmt::Display for AlcErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlcErrorCode::InvalidDevice => f.write_str("ALC_INVALID_DEVICE"),
+            AlcErrorCode::InvalidContext => f.write_str("ALC_INVALID_CONTEXT"),
+            AlcErrorCode::InvalidEnum => f.write_str("ALC_INVALID_ENUM"),
+            AlcErrorCode::InvalidValue => f.write_str("ALC_INVALID_VALUE"),
+            AlcErrorCode::OutOfMemory => f.write_str("ALC_OUT_OF_MEMORY"),
+            AlcErrorCode::Unknown(value) => write!(f, "ALC error 0x{value:04X}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error)]
 pub enum OpenalError {
     #[error("failed to load OpenAL library")]
     LibraryLoadFailed,
@@ -71,8 +175,16 @@ pub enum OpenalError {
     CreateContextFailed,
     #[error("failed to make OpenAL context current")]
     MakeContextCurrentFailed,
-    #[error("OpenAL operation failed: {0}")]
-    AlError(&'static str),
+    #[error("OpenAL call {call} failed: {code}")]
+    AlError {
+        call: &'static str,
+        code: AlErrorCode,
+    },
+    #[error("OpenAL ALC call {call} failed: {code}")]
+    AlcError {
+        call: &'static str,
+        code: AlcErrorCode,
+    },
     #[error("OpenAL buffer key already exists: {0}")]
     BufferKeyExists(BufferKey),
     #[error("OpenAL buffer key missing: {0}")]
@@ -83,15 +195,18 @@ pub enum OpenalError {
     SampleRateTooLarge,
     #[error("OpenAL returned an invalid buffer handle")]
     InvalidBufferHandle,
-    #[error("OpenAL returned an invalid source handle")]
-    InvalidSourceHandle,
     #[error("OpenAL source limit reached")]
     SourceLimitReached,
+    #[error("OpenAL voice not found: {0}")]
+    VoiceMissing(VoiceId),
+    #[error("OpenAL extension not available: {0}")]
+    ExtensionUnavailable(&'static str),
 }
 
 type AlGenBuffers = unsafe extern "C" fn(ALsizei, *mut ALuint);
 type AlDeleteBuffers = unsafe extern "C" fn(ALsizei, *const ALuint);
 type AlBufferData = unsafe extern "C" fn(ALuint, ALenum, *const ALvoid, ALsizei, ALsizei);
+type AlBufferiv = unsafe extern "C" fn(ALuint, ALenum, *const ALint);
 type AlGenSources = unsafe extern "C" fn(ALsizei, *mut ALuint);
 type AlDeleteSources = unsafe extern "C" fn(ALsizei, *const ALuint);
 type AlSourcei = unsafe extern "C" fn(ALuint, ALenum, ALint);
@@ -99,7 +214,12 @@ type AlSourcef = unsafe extern "C" fn(ALuint, ALenum, ALfloat);
 type AlSource3f = unsafe extern "C" fn(ALuint, ALenum, ALfloat, ALfloat, ALfloat);
 type AlSourcePlay = unsafe extern "C" fn(ALuint);
 type AlSourceStop = unsafe extern "C" fn(ALuint);
+type AlSourcePause = unsafe extern "C" fn(ALuint);
 type AlGetSourcei = unsafe extern "C" fn(ALuint, ALenum, *mut ALint);
+type AlGetSourcef = unsafe extern "C" fn(ALuint, ALenum, *mut ALfloat);
+type AlGetSourcedvSoft = unsafe extern "C" fn(ALuint, ALenum, *mut ALdouble);
+type AlIsExtensionPresent = unsafe extern "C" fn(*const ALchar) -> ALboolean;
+type AlGetProcAddress = unsafe extern "C" fn(*const ALchar) -> *mut c_void;
 type AlListener3f = unsafe extern "C" fn(ALenum, ALfloat, ALfloat, ALfloat);
 type AlListenerfv = unsafe extern "C" fn(ALenum, *const ALfloat);
 type AlListenerf = unsafe extern "C" fn(ALenum, ALfloat);
@@ -115,6 +235,12 @@ type AlcGetError = unsafe extern "C" fn(*mut ALCdevice) -> ALCenum;
 type AlcGetIntegerv = unsafe extern "C" fn(*mut ALCdevice, ALCenum, ALCsizei, *mut ALCint);
 type AlcIsExtensionPresent = unsafe extern "C" fn(*mut ALCdevice, *const ALCchar) -> ALCboolean;
 type AlcGetEnumValue = unsafe extern "C" fn(*mut ALCdevice, *const ALCchar) -> ALCenum;
+type AlcGetProcAddress = unsafe extern "C" fn(*mut ALCdevice, *const ALCchar) -> *mut c_void;
+type AlcGetString = unsafe extern "C" fn(*mut ALCdevice, ALCenum) -> *const ALCchar;
+/// `alcGetStringiSOFT` (`ALC_SOFT_HRTF`).
+type AlcGetStringiSoft = unsafe extern "C" fn(*mut ALCdevice, ALCenum, ALCsizei) -> *const ALCchar;
+/// `alcDevicePauseSOFT` / `alcDeviceResumeSOFT` (`ALC_SOFT_pause_device`).
+type AlcDeviceControlSoft = unsafe extern "C" fn(*mut ALCdevice);
 
 struct OpenalApi {
     _lib: Library,
@@ -122,6 +248,7 @@ struct OpenalApi {
     al_gen_buffers: AlGenBuffers,
     al_delete_buffers: AlDeleteBuffers,
     al_buffer_data: AlBufferData,
+    al_buffer_iv: AlBufferiv,
     al_gen_sources: AlGenSources,
     al_delete_sources: AlDeleteSources,
     al_source_i: AlSourcei,
@@ -129,12 +256,16 @@ struct OpenalApi {
     al_source_3f: AlSource3f,
     al_source_play: AlSourcePlay,
     al_source_stop: AlSourceStop,
+    al_source_pause: AlSourcePause,
     al_get_source_i: AlGetSourcei,
+    al_get_source_f: AlGetSourcef,
     al_listener_3f: AlListener3f,
     al_listener_fv: AlListenerfv,
     al_listener_f: AlListenerf,
     al_distance_model: AlDistanceModel,
     al_get_error: AlGetError,
+    al_is_extension_present: AlIsExtensionPresent,
+    al_get_proc_address: AlGetProcAddress,
 
     alc_open_device: AlcOpenDevice,
     alc_close_device: AlcCloseDevice,
@@ -145,6 +276,8 @@ struct OpenalApi {
     alc_get_integerv: AlcGetIntegerv,
     alc_is_extension_present: AlcIsExtensionPresent,
     alc_get_enum_value: AlcGetEnumValue,
+    alc_get_proc_address: AlcGetProcAddress,
+    alc_get_string: AlcGetString,
 }
 
 impl OpenalApi {
@@ -156,6 +289,7 @@ impl OpenalApi {
                 al_gen_buffers: load_symbol(&lib, b"alGenBuffers\0")?,
                 al_delete_buffers: load_symbol(&lib, b"alDeleteBuffers\0")?,
                 al_buffer_data: load_symbol(&lib, b"alBufferData\0")?,
+                al_buffer_iv: load_symbol(&lib, b"alBufferiv\0")?,
                 al_gen_sources: load_symbol(&lib, b"alGenSources\0")?,
                 al_delete_sources: load_symbol(&lib, b"alDeleteSources\0")?,
                 al_source_i: load_symbol(&lib, b"alSourcei\0")?,
@@ -163,12 +297,16 @@ impl OpenalApi {
                 al_source_3f: load_symbol(&lib, b"alSource3f\0")?,
                 al_source_play: load_symbol(&lib, b"alSourcePlay\0")?,
                 al_source_stop: load_symbol(&lib, b"alSourceStop\0")?,
+                al_source_pause: load_symbol(&lib, b"alSourcePause\0")?,
                 al_get_source_i: load_symbol(&lib, b"alGetSourcei\0")?,
+                al_get_source_f: load_symbol(&lib, b"alGetSourcef\0")?,
                 al_listener_3f: load_symbol(&lib, b"alListener3f\0")?,
                 al_listener_fv: load_symbol(&lib, b"alListenerfv\0")?,
                 al_listener_f: load_symbol(&lib, b"alListenerf\0")?,
                 al_distance_model: load_symbol(&lib, b"alDistanceModel\0")?,
                 al_get_error: load_symbol(&lib, b"alGetError\0")?,
+                al_is_extension_present: load_symbol(&lib, b"alIsExtensionPresent\0")?,
+                al_get_proc_address: load_symbol(&lib, b"alGetProcAddress\0")?,
                 alc_open_device: load_symbol(&lib, b"alcOpenDevice\0")?,
                 alc_close_device: load_symbol(&lib, b"alcCloseDevice\0")?,
                 alc_create_context: load_symbol(&lib, b"alcCreateContext\0")?,
@@ -178,6 +316,8 @@ impl OpenalApi {
                 alc_get_integerv: load_symbol(&lib, b"alcGetIntegerv\0")?,
                 alc_is_extension_present: load_symbol(&lib, b"alcIsExtensionPresent\0")?,
                 alc_get_enum_value: load_symbol(&lib, b"alcGetEnumValue\0")?,
+                alc_get_proc_address: load_symbol(&lib, b"alcGetProcAddress\0")?,
+                alc_get_string: load_symbol(&lib, b"alcGetString\0")?,
                 _lib: lib,
             })
         }
@@ -191,13 +331,53 @@ impl OpenalApi {
         unsafe { (self.alc_is_extension_present)(device, name.as_ptr()) == AL_TRUE as ALCboolean }
     }
 
+    /// Resolves `alGetSourcedvSOFT` on the current context when `AL_SOFT_source_latency` is
+    /// available.
+    fn load_source_latency(&self) -> Option<AlGetSourcedvSoft> {
+        let present = unsafe { (self.al_is_extension_present)(c"AL_SOFT_source_latency".as_ptr()) };
+        if present != AL_TRUE as ALboolean {
+            return None;
+        }
+        let proc = unsafe { (self.al_get_proc_address)(c"alGetSourcedvSOFT".as_ptr()) };
+        if proc.is_null() {
+            return None;
+        }
+        Some(unsafe { std::mem::transmute::<*mut c_void, AlGetSourcedvSoft>(proc) })
+    }
+
+    /// Resolves the `ALC_SOFT_pause_device` pause/resume entry points for `device`.
+    fn load_device_pause(
+        &self,
+        device: *mut ALCdevice,
+    ) -> Option<(AlcDeviceControlSoft, AlcDeviceControlSoft)> {
+        if !self.alc_has_extension(device, c"ALC_SOFT_pause_device") {
+            return None;
+        }
+        let pause = unsafe { (self.alc_get_proc_address)(device, c"alcDevicePauseSOFT".as_ptr()) };
+        let resume =
+            unsafe { (self.alc_get_proc_address)(device, c"alcDeviceResumeSOFT".as_ptr()) };
+        if pause.is_null() || resume.is_null() {
+            return None;
+        }
+        unsafe {
+            Some((
+                std::mem::transmute::<*mut c_void, AlcDeviceControlSoft>(pause),
+                std::mem::transmute::<*mut c_void, AlcDeviceControlSoft>(resume),
+            ))
+        }
+    }
+
     fn check_al(&self, context: &'static str) -> Result<(), OpenalError> {
         let err = unsafe { (self.al_get_error)() };
         if err == AL_NONE {
             return Ok(());
         }
-        warn!(al_error = err, context, "OpenAL error");
-        Err(OpenalError::AlError(context))
+        let code = AlErrorCode::from_raw(err);
+        warn!(al_error = %code, context, "OpenAL error");
+        Err(OpenalError::AlError {
+            call: context,
+            code,
+        })
     }
 
     fn check_alc(&self, device: *mut ALCdevice, context: &'static str) -> Result<(), OpenalError> {
@@ -205,8 +385,12 @@ impl OpenalApi {
         if err == AL_NONE {
             return Ok(());
         }
-        warn!(alc_error = err, context, "OpenAL ALC error");
-        Err(OpenalError::AlError(context))
+        let code = AlcErrorCode::from_raw(err);
+        warn!(alc_error = %code, context, "OpenAL ALC error");
+        Err(OpenalError::AlcError {
+            call: context,
+            code,
+        })
     }
 }
 
@@ -301,23 +485,183 @@ fn log_openal_candidates(candidate_paths: &[PathBuf], system_candidates: &[&str]
     });
 }
 
+struct Voice {
+    id: VoiceId,
+    key: BufferKey,
+    source: ALuint,
+    looping: bool,
+    bus: AudioBus,
+    priority: i32,
+    /// Gain currently applied to the source; follows `fade` while one is running.
+    gain: f32,
+    /// Requested pitch, before the engine's pitch scale.
+    pitch: f32,
+    position: Vec3,
+    paused: bool,
+    fade: Option<GainRamp>,
+    /// Fading out; the source is released when the fade completes.
+    stopping: bool,
+    started_at: Instant,
+}
+
+impl Voice {
+    fn new(id: VoiceId, key: BufferKey, source: ALuint, params: &PlayOneShotParams) -> Self {
+        let gain = sanitize_gain(params.gain);
+        let (gain, fade) = if params.fade_in.is_zero() {
+            (gain, None)
+        } else {
+            (0.0, Some(GainRamp::new(0.0, gain, params.fade_in)))
+        };
+        Self {
+            id,
+            key,
+            source,
+            looping: params.looping,
+            bus: params.bus,
+            priority: params.priority,
+            gain,
+            pitch: params.pitch,
+            position: sanitize_vec3(params.position),
+            paused: false,
+            fade,
+            stopping: false,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Advances the running fade by `elapsed`. Paused voices hold their fade.
+    fn advance_fade(&mut self, elapsed: Duration) -> FadeStep {
+        if self.paused {
+            return FadeStep::Idle;
+        }
+        let Some(ramp) = self.fade.as_mut() else {
+            return FadeStep::Idle;
+        };
+        ramp.elapsed += elapsed;
+        self.gain = ramp.gain();
+        if ramp.is_finished() {
+            self.fade = None;
+            if self.stopping {
+                return FadeStep::Stopped;
+            }
+        }
+        FadeStep::Gain
+    }
+}
+
+/// Outcome of [`Voice::advance_fade`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FadeStep {
+    /// No fade is running, or the voice is paused.
+    Idle,
+    /// The gain moved and has to be applied to the source.
+    Gain,
+    /// A fade-out completed; the voice is released.
+    Stopped,
+}
+
+/// Linear gain ramp, advanced by [`OpenalEngine::update_fades`] on every audio thread tick.
+#[derive(Debug, Clone, Copy)]
+struct GainRamp {
+    from: f32,
+    to: f32,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl GainRamp {
+    fn new(from: f32, to: f32, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            elapsed: Duration::ZERO,
+            duration,
+        }
+    }
+
+    fn gain(&self) -> f32 {
+        if self.is_finished() {
+            return self.to;
+        }
+        let t = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        self.from + (self.to - self.from) * t
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn snapshot(&self) -> VoiceFade {
+        VoiceFade {
+            target_gain: self.to,
+            remaining: self.duration.saturating_sub(self.elapsed),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Buffer {
+    id: ALuint,
+    frames: usize,
+    sample_rate_hz: u32,
+}
+
+impl Buffer {
+    fn length_seconds(&self) -> f64 {
+        self.frames as f64 / f64::from(self.sample_rate_hz.max(1))
+    }
+}
+
+/// Effective gain below which a voice is reported as virtual (about -60 dB).
+const VIRTUAL_GAIN_THRESHOLD: f32 = 0.001;
+
+/// Lowest `AL_PITCH` the engine sets; OpenAL rejects zero and negative pitch.
+const MIN_PITCH: f32 = 0.01;
+
 pub struct OpenalEngine {
     api: OpenalApi,
     device: Option<NonNull<ALCdevice>>,
     context: Option<NonNull<ALCcontext>>,
-    buffers: HashMap<BufferKey, ALuint>,
-    sources: Vec<ALuint>,
-    loop_source: Option<(BufferKey, ALuint)>,
+    buffers: HashMap<BufferKey, Buffer>,
+    /// Every source generated at startup; sources are recycled rather than deleted.
+    source_pool: Vec<ALuint>,
+    free_sources: Vec<ALuint>,
+    /// One-shot voices, oldest first.
+    voices: Vec<Voice>,
+    loop_voice: Option<Voice>,
+    events: Vec<AudioEvent>,
+    listener_position: Vec3,
     max_sources: usize,
     hrtf_active: bool,
+    /// HRTF datasets the device offers, in `ALC_HRTF_ID_SOFT` order.
+    hrtf_datasets: Vec<String>,
+    /// Dataset in use while HRTF is active.
+    hrtf_dataset: Option<String>,
+    /// Output mode asked for by the render mode, before any fallback.
+    output_mode_requested: Option<&'static str>,
     output_mode_name: Option<&'static str>,
     output_mode_raw: Option<ALCint>,
+    /// Mixing rate reported by `ALC_FREQUENCY` for the open device.
+    output_sample_rate_hz: Option<u32>,
+    /// `alGetSourcedvSOFT`, used for latency-compensated offsets.
+    source_latency: Option<AlGetSourcedvSoft>,
+    /// Whether `AL_SOFT_loop_points` is available for buffer loop regions.
+    loop_points: bool,
+    /// When [`Self::update_fades`] last advanced the running fades.
+    last_fade_update: Instant,
+    /// `alcDevicePauseSOFT` / `alcDeviceResumeSOFT`.
+    device_pause: Option<(AlcDeviceControlSoft, AlcDeviceControlSoft)>,
     distance_model: DistanceModel,
+    /// Multiplies the pitch of every voice outside [`AudioBus::Ui`].
+    pitch_scale: f32,
+    /// Multiplies the gain of every voice on each bus.
+    bus_gains: BusVolumes,
 }
 
 impl OpenalEngine {
     pub fn new(
         render_mode: AudioRenderMode,
+        hrtf_dataset: Option<&str>,
         preferred_device: Option<&str>,
         max_sources: usize,
         distance_model: DistanceModel,
@@ -333,18 +677,76 @@ impl OpenalEngine {
         let device = NonNull::new(device_ptr).ok_or(OpenalError::OpenDeviceFailed)?;
         api.check_alc(device.as_ptr(), "alcOpenDevice")?;
 
-        let attributes = build_context_attributes(&api, device.as_ptr(), render_mode);
-        let context_ptr = unsafe { (api.alc_create_context)(device.as_ptr(), attributes.as_ptr()) };
-        let context = match NonNull::new(context_ptr) {
-            Some(context) => context,
-            None => {
-                unsafe {
-                    (api.alc_close_device)(device.as_ptr());
+        let hrtf_datasets = query_hrtf_datasets(&api, device.as_ptr());
+        let hrtf_id = hrtf_dataset.and_then(|name| {
+            let id = hrtf_dataset_id(&hrtf_datasets, name);
+            if id.is_none() {
+                warn!(
+                    dataset = name,
+                    available = ?hrtf_datasets,
+                    "Unknown HRTF dataset; using the default"
+                );
+            }
+            id
+        });
+
+        // Walk the output-mode fallback chain until the device opens with the mode asked for;
+        // the last request is kept whatever the device makes of it. If no request yields a
+        // context at all, open once more without an output mode.
+        let (output_mode_requested, output_requests) =
+            output_mode_requests(&api, device.as_ptr(), render_mode);
+        let request_count = output_requests.len();
+        let attempts: Vec<Option<(&str, ALCint)>> = output_requests
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .collect();
+        let mut context = None;
+        for (index, request) in attempts.iter().enumerate() {
+            let attributes = build_context_attributes(
+                &api,
+                device.as_ptr(),
+                render_mode,
+                max_sources,
+                hrtf_id,
+                request.map(|(_, value)| value),
+            );
+            let context_ptr =
+                unsafe { (api.alc_create_context)(device.as_ptr(), attributes.as_ptr()) };
+            let Some(created) = NonNull::new(context_ptr) else {
+                let _ = api.check_alc(device.as_ptr(), "alcCreateContext");
+                continue;
+            };
+            let last = index + 1 >= request_count;
+            let (_, obtained) = query_output_mode(&api, device.as_ptr());
+            match request {
+                Some((label, value)) if !last && obtained != Some(*value) => {
+                    debug!(
+                        requested = label,
+                        obtained = ?obtained,
+                        "Output mode unavailable; trying the next fallback"
+                    );
+                    unsafe { (api.alc_destroy_context)(created.as_ptr()) };
+                }
+                _ => {
+                    context = Some(created);
+                    break;
                 }
-                return Err(OpenalError::CreateContextFailed);
             }
+        }
+        let Some(context) = context else {
+            unsafe {
+                (api.alc_close_device)(device.as_ptr());
+            }
+            return Err(OpenalError::CreateContextFailed);
         };
-        api.check_alc(device.as_ptr(), "alcCreateContext")?;
+        if let Err(err) = api.check_alc(device.as_ptr(), "alcCreateContext") {
+            unsafe {
+                (api.alc_destroy_context)(context.as_ptr());
+                (api.alc_close_device)(device.as_ptr());
+            }
+            return Err(err);
+        }
 
         if unsafe { (api.alc_make_context_current)(context.as_ptr()) } != AL_TRUE as ALCboolean {
             unsafe {
@@ -353,23 +755,60 @@ impl OpenalEngine {
             }
             return Err(OpenalError::MakeContextCurrentFailed);
         }
-        api.check_alc(device.as_ptr(), "alcMakeContextCurrent")?;
+        if let Err(err) = api.check_alc(device.as_ptr(), "alcMakeContextCurrent") {
+            unsafe {
+                (api.alc_make_context_current)(ptr::null_mut());
+                (api.alc_destroy_context)(context.as_ptr());
+                (api.alc_close_device)(device.as_ptr());
+            }
+            return Err(err);
+        }
 
         let hrtf_active = query_hrtf_active(&api, device.as_ptr());
+        let hrtf_dataset = hrtf_active
+            .then(|| query_hrtf_specifier(&api, device.as_ptr()))
+            .flatten();
         let (output_mode_name, output_mode_raw) = query_output_mode(&api, device.as_ptr());
+        let output_sample_rate_hz = query_output_sample_rate(&api, device.as_ptr());
+        let source_latency = api.load_source_latency();
+        let loop_points = unsafe { (api.al_is_extension_present)(c"AL_SOFT_loop_points".as_ptr()) }
+            == AL_TRUE as ALboolean;
+        let device_pause = api.load_device_pause(device.as_ptr());
+        let device_source_limit = query_device_source_limit(&api, device.as_ptr());
+        let source_pool = allocate_source_pool(&api, max_sources, device_source_limit);
+        info!(
+            requested = max_sources,
+            device_limit = ?device_source_limit,
+            allocated = source_pool.len(),
+            "OpenAL source pool allocated"
+        );
 
         let mut engine = Self {
             api,
             device: Some(device),
             context: Some(context),
             buffers: HashMap::new(),
-            sources: Vec::new(),
-            loop_source: None,
+            free_sources: source_pool.iter().rev().copied().collect(),
+            source_pool,
+            voices: Vec::new(),
+            loop_voice: None,
+            events: Vec::new(),
+            listener_position: Vec3::ZERO,
             max_sources,
             hrtf_active,
+            hrtf_datasets,
+            hrtf_dataset,
+            output_mode_requested,
             output_mode_name,
             output_mode_raw,
+            output_sample_rate_hz,
+            source_latency,
+            loop_points,
+            last_fade_update: Instant::now(),
+            device_pause,
             distance_model,
+            pitch_scale: 1.0,
+            bus_gains: BusVolumes::default(),
         };
 
         engine.set_distance_model(distance_model)?;
@@ -386,40 +825,165 @@ impl OpenalEngine {
         )
     }
 
+    pub fn output_mode_requested(&self) -> Option<&'static str> {
+        self.output_mode_requested
+    }
+
+    pub fn hrtf_datasets(&self) -> &[String] {
+        &self.hrtf_datasets
+    }
+
+    pub fn hrtf_dataset(&self) -> Option<&str> {
+        self.hrtf_dataset.as_deref()
+    }
+
+    pub fn output_sample_rate_hz(&self) -> Option<u32> {
+        self.output_sample_rate_hz
+    }
+
     pub fn loaded_buffers(&self) -> usize {
         self.buffers.len()
     }
 
     pub fn active_sources(&self) -> usize {
-        self.sources.len() + usize::from(self.loop_source.is_some())
+        self.voices.len() + usize::from(self.loop_voice.is_some())
+    }
+
+    pub fn source_pool_size(&self) -> usize {
+        self.source_pool.len()
+    }
+
+    /// Sources the current context allows (`ALC_MONO_SOURCES` + `ALC_STEREO_SOURCES`). Growing
+    /// the pool past this needs a new context.
+    pub fn source_limit(&self) -> Option<usize> {
+        self.device
+            .and_then(|device| query_device_source_limit(&self.api, device.as_ptr()))
+    }
+
+    /// How the pool would reach `max_sources` on this context.
+    pub fn plan_pool_resize(&self, max_sources: usize) -> PoolResize {
+        plan_pool_resize(max_sources, self.source_limit())
     }
 
+    /// Resizes the source pool to `target` sources (from [`Self::plan_pool_resize`]) for a
+    /// requested `max_sources`. Shrinking takes free sources first, then steals one-shots as a
+    /// full pool would; the loop is kept.
+    pub fn resize_source_pool(&mut self, max_sources: usize, target: usize) {
+        self.max_sources = max_sources;
+        while self.source_pool.len() > target {
+            let Ok(source) = self.acquire_or_steal_source(i32::MAX) else {
+                break;
+            };
+            unsafe { (self.api.al_delete_sources)(1, &source) };
+            let _ = self.api.check_al("alDeleteSources(shrink)");
+            self.source_pool.retain(|&pooled| pooled != source);
+        }
+        while self.source_pool.len() < target {
+            let mut source = 0;
+            unsafe { (self.api.al_gen_sources)(1, &mut source) };
+            if self.api.check_al("alGenSources(grow)").is_err() || source == 0 {
+                warn!(
+                    requested = target,
+                    allocated = self.source_pool.len(),
+                    "OpenAL refused to allocate more sources"
+                );
+                break;
+            }
+            self.source_pool.push(source);
+            self.free_sources.push(source);
+        }
+        info!(
+            requested = max_sources,
+            allocated = self.source_pool.len(),
+            "OpenAL source pool resized"
+        );
+    }
+
+    /// Closes the device and opens a new one; on error the engine is left shut down.
     pub fn recreate(
         &mut self,
         render_mode: AudioRenderMode,
+        hrtf_dataset: Option<&str>,
         preferred_device: Option<&str>,
+        max_sources: usize,
         distance_model: DistanceModel,
     ) -> Result<(), OpenalError> {
+        // One-shots do not survive a device reopen; the loop is rebuilt by the runtime.
+        let mut events = std::mem::take(&mut self.events);
+        events.extend(self.voices.iter().map(|voice| AudioEvent::VoiceDropped {
+            voice: voice.id,
+            key: voice.key,
+        }));
         self.shutdown();
 
-        *self = Self::new(
+        let result = Self::new(
             render_mode,
+            hrtf_dataset,
             preferred_device,
-            self.max_sources,
+            max_sources,
             distance_model,
-        )?;
-        Ok(())
+        );
+        match result {
+            Ok(engine) => {
+                *self = engine;
+                self.events = events;
+                Ok(())
+            }
+            Err(err) => {
+                self.events = events;
+                Err(err)
+            }
+        }
     }
 
-    pub fn set_muted(&self, muted: bool) -> Result<(), OpenalError> {
-        let gain = if muted { 0.0 } else { 1.0 };
-        unsafe { (self.api.al_listener_f)(AL_GAIN, gain) };
+    /// Sets the listener gain, which scales every voice.
+    pub fn set_master_gain(&self, gain: f32) -> Result<(), OpenalError> {
+        unsafe { (self.api.al_listener_f)(AL_GAIN, sanitize_gain(gain)) };
         self.api.check_al("alListenerf(AL_GAIN)")?;
         Ok(())
     }
 
-    pub fn set_listener(&self, listener: ListenerFrame) -> Result<(), OpenalError> {
+    /// Scales the pitch (and so the playback speed) of every voice outside [`AudioBus::Ui`],
+    /// including ones already playing.
+    pub fn set_pitch_scale(&mut self, scale: f32) -> Result<(), OpenalError> {
+        self.pitch_scale = sanitize_pitch(scale);
+        for voice in self.voices.iter().chain(self.loop_voice.as_ref()) {
+            let pitch = self.source_pitch(voice.bus, voice.pitch);
+            unsafe { (self.api.al_source_f)(voice.source, AL_PITCH, pitch) };
+        }
+        self.api.check_al("alSourcef(AL_PITCH)")
+    }
+
+    /// Sets the gain of `bus`, including voices already playing on it.
+    pub fn set_bus_gain(&mut self, bus: AudioBus, gain: f32) -> Result<(), OpenalError> {
+        self.bus_gains.set(bus, sanitize_gain(gain));
+        for voice in self.voices.iter().chain(self.loop_voice.as_ref()) {
+            if voice.bus == bus {
+                let gain = self.source_gain(bus, voice.gain);
+                unsafe { (self.api.al_source_f)(voice.source, AL_GAIN, gain) };
+            }
+        }
+        self.api.check_al("alSourcef(AL_GAIN)")
+    }
+
+    /// `AL_GAIN` for a voice on `bus` at `gain`.
+    fn source_gain(&self, bus: AudioBus, gain: f32) -> f32 {
+        gain * self.bus_gains.get(bus)
+    }
+
+    /// `AL_PITCH` for a voice on `bus` requesting `pitch`.
+    fn source_pitch(&self, bus: AudioBus, pitch: f32) -> f32 {
+        let pitch = sanitize_pitch(pitch);
+        if bus == AudioBus::Ui {
+            pitch
+        } else {
+            pitch * self.pitch_scale
+        }
+    }
+
+    pub fn set_listener(&mut self, listener: ListenerFrame) -> Result<(), OpenalError> {
         let position = sanitize_vec3(listener.position);
+        self.listener_position = position;
         let velocity = sanitize_vec3(listener.velocity);
         unsafe {
             (self.api.al_listener_3f)(AL_POSITION, position.x, position.y, position.z);
@@ -436,6 +1000,26 @@ impl OpenalEngine {
         Ok(())
     }
 
+    pub fn set_voice_transform(
+        &mut self,
+        voice: VoiceId,
+        position: Vec3,
+        velocity: Vec3,
+    ) -> Result<(), OpenalError> {
+        let position = sanitize_vec3(position);
+        let velocity = sanitize_vec3(velocity);
+        let entry = self
+            .voice_mut(voice)
+            .ok_or(OpenalError::VoiceMissing(voice))?;
+        entry.position = position;
+        let source = entry.source;
+        unsafe {
+            (self.api.al_source_3f)(source, AL_POSITION, position.x, position.y, position.z);
+            (self.api.al_source_3f)(source, AL_VELOCITY, velocity.x, velocity.y, velocity.z);
+        }
+        self.api.check_al("alSource3f(voice transform)")
+    }
+
     pub fn set_distance_model(&mut self, model: DistanceModel) -> Result<(), OpenalError> {
         let value = match model {
             DistanceModel::None => AL_NONE,
@@ -460,7 +1044,101 @@ impl OpenalEngine {
         if self.buffers.contains_key(&key) {
             return Err(OpenalError::BufferKeyExists(key));
         }
+        let buffer = self.upload_buffer(decoded)?;
+        self.buffers.insert(key, buffer);
+        Ok(())
+    }
+
+    /// Swaps the audio behind `key` (hot reload), creating the buffer if it does not exist.
+    ///
+    /// Voices playing the old data are stopped, reattached to the new buffer and restarted at the
+    /// same sample offset: wrapped for loops, finished for one-shots past the new end. Paused
+    /// voices stay paused. The old buffer is deleted only after every source has let go of it.
+    ///
+    /// The outer error means the upload failed and nothing changed. Once the new buffer is in
+    /// place the swap stands, and the inner result reports failures restarting voices or deleting
+    /// the old buffer.
+    pub fn replace_buffer(
+        &mut self,
+        key: BufferKey,
+        decoded: &DecodedAudioMono16,
+    ) -> Result<Result<(), OpenalError>, OpenalError> {
+        let buffer = self.upload_buffer(decoded)?;
+        let Some(old) = self.buffers.insert(key, buffer) else {
+            return Ok(Ok(()));
+        };
 
+        let users: Vec<VoiceId> = self
+            .voices
+            .iter()
+            .chain(self.loop_voice.as_ref())
+            .filter(|voice| voice.key == key)
+            .map(|voice| voice.id)
+            .collect();
+        let mut finished = Vec::new();
+        let mut first_error = None;
+        for id in users {
+            let Some(voice) = self.voice(id) else {
+                continue;
+            };
+            let (source, looping, paused) = (voice.source, voice.looping, voice.paused);
+            let mut state: ALint = 0;
+            let mut sample: ALint = 0;
+            unsafe {
+                (self.api.al_get_source_i)(source, AL_SOURCE_STATE, &mut state);
+                (self.api.al_get_source_i)(source, AL_SAMPLE_OFFSET, &mut sample);
+                (self.api.al_source_stop)(source);
+            }
+            let sample = usize::try_from(sample).unwrap_or(0);
+            let Some(sample) = reattach_offset(looping, state as ALenum, sample, buffer.frames)
+            else {
+                // Released (and detached) below, like any other finished voice.
+                finished.push(id);
+                continue;
+            };
+            // A paused voice is left stopped at `sample`: cleanup skips paused voices and
+            // resuming plays from the stored offset.
+            unsafe {
+                (self.api.al_source_i)(source, AL_BUFFER, buffer.id as ALint);
+                (self.api.al_source_i)(source, AL_SAMPLE_OFFSET, sample as ALint);
+                if !paused {
+                    (self.api.al_source_play)(source);
+                }
+            }
+            if let Err(err) = self.api.check_al("alSourcePlay(reload)") {
+                first_error.get_or_insert(err);
+            }
+        }
+        for id in finished {
+            self.finish_voice(id);
+        }
+
+        unsafe { (self.api.al_delete_buffers)(1, &old.id) };
+        let deleted = self.api.check_al("alDeleteBuffers(reload)");
+        Ok(first_error.map_or(deleted, Err))
+    }
+
+    /// Deletes buffer `key`, finishing every voice (and the loop) that still plays it.
+    pub fn delete_buffer(&mut self, key: BufferKey) -> Result<(), OpenalError> {
+        let Some(buffer) = self.buffers.remove(&key) else {
+            return Err(OpenalError::BufferKeyMissing(key));
+        };
+        let users: Vec<VoiceId> = self
+            .voices
+            .iter()
+            .chain(self.loop_voice.as_ref())
+            .filter(|voice| voice.key == key)
+            .map(|voice| voice.id)
+            .collect();
+        for id in users {
+            self.finish_voice(id);
+        }
+        unsafe { (self.api.al_delete_buffers)(1, &buffer.id) };
+        self.api.check_al("alDeleteBuffers")
+    }
+
+    /// Generates an AL buffer and fills it with `decoded`; the buffer is deleted again on failure.
+    fn upload_buffer(&self, decoded: &DecodedAudioMono16) -> Result<Buffer, OpenalError> {
         let data_len = decoded
             .samples
             .len()
@@ -486,62 +1164,103 @@ impl OpenalEngine {
                 sample_rate,
             );
         }
-        self.api.check_al("alBufferData")?;
+        if let Err(err) = self.api.check_al("alBufferData") {
+            unsafe { (self.api.al_delete_buffers)(1, &buffer) };
+            let _ = self.api.check_al("alDeleteBuffers(failed upload)");
+            return Err(err);
+        }
+        if let Some(frames) = &decoded.loop_frames {
+            self.set_loop_points(buffer, frames);
+        }
 
-        self.buffers.insert(key, buffer);
-        Ok(())
+        Ok(Buffer {
+            id: buffer,
+            frames: decoded.samples.len(),
+            sample_rate_hz: decoded.sample_rate_hz,
+        })
+    }
+
+    /// Restricts looping to `frames`. Best effort: without `AL_SOFT_loop_points` the whole buffer
+    /// loops.
+    fn set_loop_points(&self, buffer: ALuint, frames: &Range<usize>) {
+        if !self.loop_points {
+            debug!("AL_SOFT_loop_points unavailable; looping whole buffer");
+            return;
+        }
+        let (Ok(start), Ok(end)) = (ALint::try_from(frames.start), ALint::try_from(frames.end))
+        else {
+            return;
+        };
+        let points = [start, end];
+        unsafe { (self.api.al_buffer_iv)(buffer, AL_LOOP_POINTS_SOFT, points.as_ptr()) };
+        if let Err(err) = self.api.check_al("alBufferiv(AL_LOOP_POINTS_SOFT)") {
+            warn!(error = %err, "Failed to set buffer loop points");
+        }
     }
 
     pub fn play_one_shot(
         &mut self,
+        voice: VoiceId,
         key: BufferKey,
         params: PlayOneShotParams,
     ) -> Result<(), OpenalError> {
-        if self.sources.len() + usize::from(self.loop_source.is_some()) >= self.max_sources {
-            return Err(OpenalError::SourceLimitReached);
-        }
-        let Some(&buffer) = self.buffers.get(&key) else {
+        let Some(buffer) = self.buffers.get(&key).map(|buffer| buffer.id) else {
             return Err(OpenalError::BufferKeyMissing(key));
         };
+        let source = self.acquire_or_steal_source(params.priority)?;
+        let entry = Voice::new(voice, key, source, &params);
 
-        let mut source = 0;
-        unsafe { (self.api.al_gen_sources)(1, &mut source) };
-        self.api.check_al("alGenSources")?;
-        if source == 0 {
-            return Err(OpenalError::InvalidSourceHandle);
-        }
-
-        let position = sanitize_vec3(params.position);
+        let position = entry.position;
+        let gain = self.source_gain(params.bus, entry.gain);
+        let pitch = self.source_pitch(params.bus, params.pitch);
         unsafe {
             (self.api.al_source_i)(source, AL_BUFFER, buffer as ALint);
-            (self.api.al_source_f)(source, AL_GAIN, params.gain);
-            (self.api.al_source_f)(source, AL_PITCH, params.pitch);
+            (self.api.al_source_i)(source, AL_LOOPING, ALint::from(params.looping));
+            (self.api.al_source_f)(source, AL_GAIN, gain);
+            (self.api.al_source_f)(source, AL_PITCH, pitch);
             (self.api.al_source_3f)(source, AL_POSITION, position.x, position.y, position.z);
             (self.api.al_source_play)(source);
         }
-        self.api.check_al("alSourcePlay")?;
+        if let Err(err) = self.api.check_al("alSourcePlay") {
+            self.release_source(source);
+            return Err(err);
+        }
 
-        self.sources.push(source);
+        self.voices.push(entry);
         Ok(())
     }
 
     pub fn start_loop(
         &mut self,
+        voice: VoiceId,
         key: BufferKey,
         params: PlayOneShotParams,
     ) -> Result<(), OpenalError> {
-        let Some(&buffer) = self.buffers.get(&key) else {
+        let Some(buffer) = self.buffers.get(&key).map(|buffer| buffer.id) else {
             return Err(OpenalError::BufferKeyMissing(key));
         };
 
         let position = sanitize_vec3(params.position);
-        if let Some((existing_key, source)) = self.loop_source {
-            if existing_key == key {
+        let pitch = self.source_pitch(params.bus, params.pitch);
+        let bus_gain = self.bus_gains.get(params.bus);
+        if let Some(existing) = self.loop_voice.as_mut() {
+            if existing.key == key {
+                let source = existing.source;
+                let replaced = std::mem::replace(existing, Voice::new(voice, key, source, &params));
+                existing.looping = true;
+                let gain = existing.gain * bus_gain;
+                let replaced = replaced.id;
+                if replaced != voice {
+                    self.events.push(AudioEvent::VoiceFinished {
+                        voice: replaced,
+                        key,
+                    });
+                }
                 unsafe {
                     (self.api.al_source_i)(source, AL_BUFFER, buffer as ALint);
                     (self.api.al_source_i)(source, AL_LOOPING, AL_TRUE as ALint);
-                    (self.api.al_source_f)(source, AL_GAIN, params.gain);
-                    (self.api.al_source_f)(source, AL_PITCH, params.pitch);
+                    (self.api.al_source_f)(source, AL_GAIN, gain);
+                    (self.api.al_source_f)(source, AL_PITCH, pitch);
                     (self.api.al_source_3f)(
                         source,
                         AL_POSITION,
@@ -554,73 +1273,439 @@ impl OpenalEngine {
                 self.api.check_al("alSourcePlay(loop)")?;
                 return Ok(());
             }
-            self.stop_loop()?;
-        }
-
-        if self.sources.len() + usize::from(self.loop_source.is_some()) >= self.max_sources {
-            return Err(OpenalError::SourceLimitReached);
+            // The outgoing loop fades out over the new loop's fade-in, i.e. a crossfade.
+            self.stop_loop(params.fade_in)?;
         }
 
-        let mut source = 0;
-        unsafe { (self.api.al_gen_sources)(1, &mut source) };
-        self.api.check_al("alGenSources(loop)")?;
-        if source == 0 {
-            return Err(OpenalError::InvalidSourceHandle);
-        }
+        let source = self.acquire_or_steal_source(params.priority)?;
+        let entry = Voice {
+            looping: true,
+            ..Voice::new(voice, key, source, &params)
+        };
 
         unsafe {
             (self.api.al_source_i)(source, AL_BUFFER, buffer as ALint);
             (self.api.al_source_i)(source, AL_LOOPING, AL_TRUE as ALint);
-            (self.api.al_source_f)(source, AL_GAIN, params.gain);
-            (self.api.al_source_f)(source, AL_PITCH, params.pitch);
+            (self.api.al_source_f)(source, AL_GAIN, entry.gain * bus_gain);
+            (self.api.al_source_f)(source, AL_PITCH, pitch);
             (self.api.al_source_3f)(source, AL_POSITION, position.x, position.y, position.z);
             (self.api.al_source_play)(source);
         }
-        self.api.check_al("alSourcePlay(loop)")?;
+        if let Err(err) = self.api.check_al("alSourcePlay(loop)") {
+            self.release_source(source);
+            return Err(err);
+        }
 
-        self.loop_source = Some((key, source));
+        self.loop_voice = Some(entry);
         Ok(())
     }
 
-    pub fn stop_loop(&mut self) -> Result<(), OpenalError> {
-        let Some((_, source)) = self.loop_source.take() else {
-            return Ok(());
-        };
+    pub fn stop_loop(&mut self, fade_out: Duration) -> Result<(), OpenalError> {
+        match self.loop_voice.as_ref().map(|voice| voice.id) {
+            Some(voice) => self.stop_voice(voice, fade_out),
+            None => Ok(()),
+        }
+    }
 
-        unsafe {
-            (self.api.al_source_stop)(source);
-            (self.api.al_delete_sources)(1, &source);
+    /// Stops `voice` after fading it out over `fade_out`; its source is only released once the
+    /// fade completes. A zero duration, or a paused voice, stops immediately.
+    pub fn stop_voice(&mut self, voice: VoiceId, fade_out: Duration) -> Result<(), OpenalError> {
+        // A fading loop moves out of the loop slot so a new loop can start meanwhile.
+        if let Some(entry) = self.loop_voice.take_if(|entry| entry.id == voice) {
+            self.voices.push(entry);
+        }
+        let entry = self
+            .voice_mut(voice)
+            .ok_or(OpenalError::VoiceMissing(voice))?;
+        if fade_out.is_zero() || entry.paused {
+            self.finish_voice(voice);
+            return Ok(());
+        }
+        if !entry.stopping {
+            entry.stopping = true;
+            entry.fade = Some(GainRamp::new(entry.gain, 0.0, fade_out));
         }
-        let _ = self.api.check_al("alDeleteSources(loop)");
         Ok(())
     }
 
+    /// Ramps `voice` to `gain` over `duration`, replacing any running fade. Voices that are
+    /// already fading out keep their stop fade.
+    pub fn fade_voice(
+        &mut self,
+        voice: VoiceId,
+        gain: f32,
+        duration: Duration,
+    ) -> Result<(), OpenalError> {
+        let entry = self
+            .voice_mut(voice)
+            .ok_or(OpenalError::VoiceMissing(voice))?;
+        if entry.stopping {
+            return Ok(());
+        }
+        let gain = sanitize_gain(gain);
+        if !duration.is_zero() {
+            entry.fade = Some(GainRamp::new(entry.gain, gain, duration));
+            return Ok(());
+        }
+        entry.fade = None;
+        entry.gain = gain;
+        let (source, bus) = (entry.source, entry.bus);
+        let gain = self.source_gain(bus, gain);
+        unsafe { (self.api.al_source_f)(source, AL_GAIN, gain) };
+        self.api.check_al("alSourcef(AL_GAIN)")
+    }
+
+    /// Advances every running fade by the time since the previous call and releases voices whose
+    /// fade-out has finished. Paused voices hold their fade.
+    pub fn update_fades(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.last_fade_update);
+        self.last_fade_update = now;
+
+        let bus_gains = self.bus_gains;
+        let mut finished = Vec::new();
+        for voice in self.voices.iter_mut().chain(self.loop_voice.as_mut()) {
+            match voice.advance_fade(elapsed) {
+                FadeStep::Idle => {}
+                FadeStep::Gain => {
+                    let gain = voice.gain * bus_gains.get(voice.bus);
+                    unsafe { (self.api.al_source_f)(voice.source, AL_GAIN, gain) };
+                }
+                FadeStep::Stopped => finished.push(voice.id),
+            }
+        }
+        let _ = self.api.check_al("alSourcef(fade)");
+
+        for voice in finished {
+            self.finish_voice(voice);
+        }
+    }
+
+    /// Releases the source of `voice` and reports it finished.
+    fn finish_voice(&mut self, voice: VoiceId) {
+        let entry = if let Some(entry) = self.loop_voice.take_if(|entry| entry.id == voice) {
+            entry
+        } else if let Some(index) = self.voices.iter().position(|entry| entry.id == voice) {
+            self.voices.remove(index)
+        } else {
+            return;
+        };
+        self.release_source(entry.source);
+        self.events.push(AudioEvent::VoiceFinished {
+            voice: entry.id,
+            key: entry.key,
+        });
+    }
+
     pub fn cleanup_finished_sources(&mut self) {
         let mut i = 0;
-        while i < self.sources.len() {
-            let source = self.sources[i];
+        while i < self.voices.len() {
+            let source = self.voices[i].source;
             let mut state: ALint = 0;
             unsafe { (self.api.al_get_source_i)(source, AL_SOURCE_STATE, &mut state) };
             if self.api.check_al("alGetSourcei").is_err() {
                 i += 1;
                 continue;
             }
-            if state as ALenum == AL_STOPPED {
-                unsafe { (self.api.al_delete_sources)(1, &source) };
-                let _ = self.api.check_al("alDeleteSources");
-                self.sources.swap_remove(i);
+            if is_voice_finished(self.voices[i].paused, state as ALenum) {
+                let voice = self.voices.remove(i);
+                self.release_source(voice.source);
+                self.events.push(AudioEvent::VoiceFinished {
+                    voice: voice.id,
+                    key: voice.key,
+                });
             } else {
                 i += 1;
             }
         }
     }
 
+    pub fn pause_voice(&mut self, voice: VoiceId) -> Result<(), OpenalError> {
+        self.set_voice_paused(voice, true).map(drop)
+    }
+
+    pub fn resume_voice(&mut self, voice: VoiceId) -> Result<(), OpenalError> {
+        self.set_voice_paused(voice, false).map(drop)
+    }
+
+    /// Pauses or resumes every voice matched by `filter` and returns how many changed. Keeps
+    /// going past failures and reports the first one.
+    pub fn set_voices_paused(
+        &mut self,
+        filter: VoiceFilter,
+        paused: bool,
+    ) -> Result<usize, OpenalError> {
+        let targets = pause_targets(
+            self.voices.iter().chain(self.loop_voice.as_ref()),
+            filter,
+            paused,
+        );
+        let mut changed = 0;
+        let mut first_error = None;
+        for voice in targets {
+            match self.set_voice_paused(voice, paused) {
+                Ok(true) => changed += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        first_error.map_or(Ok(changed), Err)
+    }
+
+    /// Returns `true` if the voice changed state.
+    fn set_voice_paused(&mut self, voice: VoiceId, paused: bool) -> Result<bool, OpenalError> {
+        let entry = self.voice(voice).ok_or(OpenalError::VoiceMissing(voice))?;
+        if entry.paused == paused {
+            return Ok(false);
+        }
+        let (source, key) = (entry.source, entry.key);
+        if paused {
+            unsafe { (self.api.al_source_pause)(source) };
+            self.api.check_al("alSourcePause")?;
+        } else {
+            unsafe { (self.api.al_source_play)(source) };
+            self.api.check_al("alSourcePlay(resume)")?;
+        }
+        if let Some(entry) = self.voice_mut(voice) {
+            entry.paused = paused;
+        }
+        self.events.push(if paused {
+            AudioEvent::VoicePaused { voice, key }
+        } else {
+            AudioEvent::VoiceResumed { voice, key }
+        });
+        Ok(true)
+    }
+
+    /// Stops or restarts mixing for the whole device (`ALC_SOFT_pause_device`), e.g. while the
+    /// app is suspended. Sources keep their state.
+    pub fn set_device_paused(&mut self, paused: bool) -> Result<(), OpenalError> {
+        let (Some(device), Some((pause, resume))) = (self.device, self.device_pause) else {
+            return Err(OpenalError::ExtensionUnavailable("ALC_SOFT_pause_device"));
+        };
+        if paused {
+            unsafe { pause(device.as_ptr()) };
+            self.api.check_alc(device.as_ptr(), "alcDevicePauseSOFT")
+        } else {
+            unsafe { resume(device.as_ptr()) };
+            self.api.check_alc(device.as_ptr(), "alcDeviceResumeSOFT")
+        }
+    }
+
+    /// Returns `false` once the device reports it was disconnected (`ALC_EXT_disconnect`).
+    /// Devices without the extension are always treated as connected.
+    pub fn is_device_connected(&self) -> bool {
+        let Some(device) = self.device else {
+            return false;
+        };
+        if !self
+            .api
+            .alc_has_extension(device.as_ptr(), c"ALC_EXT_disconnect")
+        {
+            return true;
+        }
+        let mut connected: ALCint = 1;
+        unsafe { (self.api.alc_get_integerv)(device.as_ptr(), ALC_CONNECTED, 1, &mut connected) };
+        if self
+            .api
+            .check_alc(device.as_ptr(), "alcGetIntegerv(ALC_CONNECTED)")
+            .is_err()
+        {
+            return true;
+        }
+        connected != 0
+    }
+
+    /// Drains lifecycle events discovered by the engine (finished and stolen voices).
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, AudioEvent> {
+        self.events.drain(..)
+    }
+
+    fn voice(&self, voice: VoiceId) -> Option<&Voice> {
+        self.voices
+            .iter()
+            .chain(self.loop_voice.as_ref())
+            .find(|candidate| candidate.id == voice)
+    }
+
+    /// Returns the audible playback position of `voice`.
+    pub fn voice_offset(&self, voice: VoiceId) -> Result<VoiceOffset, OpenalError> {
+        let voice = self.voice(voice).ok_or(OpenalError::VoiceMissing(voice))?;
+        self.source_offset(voice)
+    }
+
+    /// Seeks `voice`; takes effect immediately on a playing source.
+    pub fn set_voice_offset(
+        &mut self,
+        voice: VoiceId,
+        offset: PlaybackOffset,
+    ) -> Result<(), OpenalError> {
+        let source = self
+            .voice(voice)
+            .ok_or(OpenalError::VoiceMissing(voice))?
+            .source;
+        match offset {
+            PlaybackOffset::Seconds(seconds) => {
+                unsafe { (self.api.al_source_f)(source, AL_SEC_OFFSET, seconds.max(0.0)) };
+                self.api.check_al("alSourcef(AL_SEC_OFFSET)")
+            }
+            PlaybackOffset::Samples(sample) => {
+                let sample = ALint::try_from(sample).unwrap_or(ALint::MAX);
+                unsafe { (self.api.al_source_i)(source, AL_SAMPLE_OFFSET, sample) };
+                self.api.check_al("alSourcei(AL_SAMPLE_OFFSET)")
+            }
+        }
+    }
+
+    /// Reads the source offset, subtracting the device latency when `AL_SOFT_source_latency` is
+    /// available so the result tracks what is audible rather than what has been mixed.
+    fn source_offset(&self, voice: &Voice) -> Result<VoiceOffset, OpenalError> {
+        let (sample_rate_hz, length_seconds) =
+            self.buffers.get(&voice.key).map_or((0, 0.0), |buffer| {
+                (buffer.sample_rate_hz, buffer.length_seconds())
+            });
+
+        let (mixed, latency_seconds) = match self.source_latency {
+            Some(get_source_dv) => {
+                let mut values: [ALdouble; 2] = [0.0; 2];
+                unsafe {
+                    get_source_dv(
+                        voice.source,
+                        AL_SEC_OFFSET_LATENCY_SOFT,
+                        values.as_mut_ptr(),
+                    )
+                };
+                self.api
+                    .check_al("alGetSourcedvSOFT(AL_SEC_OFFSET_LATENCY_SOFT)")?;
+                (values[0], Some(values[1]))
+            }
+            None => {
+                let mut seconds: ALfloat = 0.0;
+                unsafe { (self.api.al_get_source_f)(voice.source, AL_SEC_OFFSET, &mut seconds) };
+                self.api.check_al("alGetSourcef(AL_SEC_OFFSET)")?;
+                (f64::from(seconds), None)
+            }
+        };
+
+        let mut seconds = mixed - latency_seconds.unwrap_or(0.0);
+        if seconds < 0.0 && voice.looping && length_seconds > 0.0 {
+            seconds += length_seconds;
+        }
+        let seconds = seconds.max(0.0);
+        Ok(VoiceOffset {
+            seconds,
+            sample: (seconds * f64::from(sample_rate_hz)) as u64,
+            latency_seconds,
+            length_seconds,
+        })
+    }
+
+    fn voice_mut(&mut self, voice: VoiceId) -> Option<&mut Voice> {
+        self.voices
+            .iter_mut()
+            .chain(self.loop_voice.as_mut())
+            .find(|candidate| candidate.id == voice)
+    }
+
+    pub fn voice_snapshot(&self) -> AudioVoiceSnapshot {
+        let now = Instant::now();
+        let voices = self
+            .voices
+            .iter()
+            .chain(self.loop_voice.as_ref())
+            .map(|voice| {
+                let distance = voice.position.distance(self.listener_position);
+                let attenuation = distance_attenuation(self.distance_model, distance);
+                let state = if voice.paused {
+                    VoiceState::Paused
+                } else if voice.stopping {
+                    VoiceState::Stopping
+                } else if self.source_gain(voice.bus, voice.gain) * attenuation
+                    < VIRTUAL_GAIN_THRESHOLD
+                {
+                    VoiceState::Virtual
+                } else {
+                    VoiceState::Playing
+                };
+                VoiceSnapshot {
+                    voice: voice.id,
+                    key: voice.key,
+                    bus: voice.bus,
+                    looping: voice.looping,
+                    position: voice.position,
+                    distance,
+                    attenuation,
+                    gain: voice.gain,
+                    priority: voice.priority,
+                    state,
+                    fade: voice.fade.as_ref().map(GainRamp::snapshot),
+                    age: now.saturating_duration_since(voice.started_at),
+                    offset: self.source_offset(voice).unwrap_or_default(),
+                }
+            })
+            .collect();
+        AudioVoiceSnapshot {
+            listener_position: self.listener_position,
+            voices,
+        }
+    }
+
+    /// Takes a free source from the pool. When the pool is exhausted, steals the lowest-priority
+    /// one-shot (voices already fading out first, then oldest first among equals) if its priority
+    /// does not exceed `priority`. The active loop is never stolen.
+    fn acquire_or_steal_source(&mut self, priority: i32) -> Result<ALuint, OpenalError> {
+        if let Ok(source) = self.acquire_source() {
+            return Ok(source);
+        }
+        let victim = steal_victim(&self.voices, priority).ok_or(OpenalError::SourceLimitReached)?;
+        let stolen = self.voices.remove(victim);
+        self.release_source(stolen.source);
+        self.events.push(AudioEvent::VoiceStolen {
+            voice: stolen.id,
+            key: stolen.key,
+        });
+        self.acquire_source()
+    }
+
+    fn acquire_source(&mut self) -> Result<ALuint, OpenalError> {
+        self.free_sources
+            .pop()
+            .ok_or(OpenalError::SourceLimitReached)
+    }
+
+    /// Stops a pooled source, detaches its buffer and restores default parameters so the next
+    /// voice starts from a clean slate.
+    fn release_source(&mut self, source: ALuint) {
+        unsafe {
+            (self.api.al_source_stop)(source);
+            (self.api.al_source_i)(source, AL_BUFFER, 0);
+            (self.api.al_source_i)(source, AL_LOOPING, 0);
+            (self.api.al_source_f)(source, AL_GAIN, 1.0);
+            (self.api.al_source_f)(source, AL_PITCH, 1.0);
+            (self.api.al_source_3f)(source, AL_POSITION, 0.0, 0.0, 0.0);
+            (self.api.al_source_3f)(source, AL_VELOCITY, 0.0, 0.0, 0.0);
+        }
+        let _ = self.api.check_al("alSourceStop(release)");
+        self.free_sources.push(source);
+    }
+
     pub fn shutdown(&mut self) {
-        let _ = self.stop_loop();
-        for source in self.sources.drain(..) {
-            unsafe { (self.api.al_delete_sources)(1, &source) };
+        if let Some(voice) = self.loop_voice.take() {
+            unsafe { (self.api.al_source_stop)(voice.source) };
         }
-        for buffer in self.buffers.drain().map(|(_, buffer)| buffer) {
+        for voice in self.voices.drain(..) {
+            unsafe { (self.api.al_source_stop)(voice.source) };
+        }
+        self.free_sources.clear();
+        if !self.source_pool.is_empty() {
+            let count = al_size_from_usize(self.source_pool.len()).unwrap_or(ALsizei::MAX);
+            unsafe { (self.api.al_delete_sources)(count, self.source_pool.as_ptr()) };
+            let _ = self.api.check_al("alDeleteSources(pool)");
+            self.source_pool.clear();
+        }
+        for buffer in self.buffers.drain().map(|(_, buffer)| buffer.id) {
             unsafe { (self.api.al_delete_buffers)(1, &buffer) };
         }
 
@@ -641,8 +1726,10 @@ impl OpenalEngine {
         }
 
         self.hrtf_active = false;
+        self.output_mode_requested = None;
         self.output_mode_name = None;
         self.output_mode_raw = None;
+        self.output_sample_rate_hz = None;
         self.distance_model = DistanceModel::None;
     }
 }
@@ -655,6 +1742,71 @@ impl Drop for OpenalEngine {
     }
 }
 
+/// Index of the one-shot to steal for a new voice of `priority`: voices already fading out first,
+/// then the lowest priority, then the oldest. Voices above `priority` are never stolen.
+fn steal_victim(voices: &[Voice], priority: i32) -> Option<usize> {
+    voices
+        .iter()
+        .enumerate()
+        .filter(|(_, voice)| voice.priority <= priority)
+        .min_by_key(|(_, voice)| (!voice.stopping, voice.priority, voice.started_at))
+        .map(|(index, _)| index)
+}
+
+/// Index of the dataset called `name` for `ALC_HRTF_ID_SOFT`, or `None` to let the device pick
+/// its default.
+fn hrtf_dataset_id(datasets: &[String], name: &str) -> Option<usize> {
+    datasets.iter().position(|dataset| dataset == name)
+}
+
+/// Voices matched by `filter` that are not already in the requested pause state.
+fn pause_targets<'a>(
+    voices: impl Iterator<Item = &'a Voice>,
+    filter: VoiceFilter,
+    paused: bool,
+) -> Vec<VoiceId> {
+    voices
+        .filter(|voice| voice.paused != paused && filter.matches(voice.bus))
+        .map(|voice| voice.id)
+        .collect()
+}
+
+/// Sample offset a voice resumes at after its buffer is replaced by one `frames` long, or
+/// `None` if the voice is done: loops wrap, one-shots past the new end finish.
+fn reattach_offset(looping: bool, state: ALenum, sample: usize, frames: usize) -> Option<usize> {
+    if looping {
+        Some(sample % frames.max(1))
+    } else if state == AL_STOPPED || sample >= frames {
+        None
+    } else {
+        Some(sample)
+    }
+}
+
+/// Whether a one-shot in `state` has played out. Paused voices can sit in `AL_STOPPED` after a
+/// buffer swap and are not finished.
+fn is_voice_finished(paused: bool, state: ALenum) -> bool {
+    !paused && state == AL_STOPPED
+}
+
+/// How the source pool reaches a new `max_sources`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PoolResize {
+    /// Grow or shrink the pool in place to this many sources.
+    InPlace(usize),
+    /// The context allows fewer sources than requested; the device has to be reopened, which
+    /// drops every playing one-shot.
+    Reopen,
+}
+
+/// Plans a pool change to `max_sources` under the context's source `limit`, if known.
+fn plan_pool_resize(max_sources: usize, limit: Option<usize>) -> PoolResize {
+    match limit {
+        Some(limit) if max_sources > limit => PoolResize::Reopen,
+        _ => PoolResize::InPlace(max_sources),
+    }
+}
+
 fn al_size_from_usize(value: usize) -> Option<ALsizei> {
     i32::try_from(value).ok().map(|value| value as ALsizei)
 }
@@ -663,6 +1815,50 @@ fn al_size_from_u32(value: u32) -> Option<ALsizei> {
     i32::try_from(value).ok().map(|value| value as ALsizei)
 }
 
+/// Gain applied by OpenAL's distance model for a source at `distance`, using the default
+/// source parameters (reference distance 1, rolloff 1, unbounded max distance).
+fn distance_attenuation(model: DistanceModel, distance: f32) -> f32 {
+    const REFERENCE: f32 = 1.0;
+    const ROLLOFF: f32 = 1.0;
+    const MAX_DISTANCE: f32 = f32::MAX;
+
+    let clamped = distance.clamp(REFERENCE, MAX_DISTANCE);
+    let gain = match model {
+        DistanceModel::None => 1.0,
+        DistanceModel::Inverse => REFERENCE / (REFERENCE + ROLLOFF * (distance - REFERENCE)),
+        DistanceModel::InverseClamped => REFERENCE / (REFERENCE + ROLLOFF * (clamped - REFERENCE)),
+        DistanceModel::Linear => {
+            1.0 - ROLLOFF * (distance - REFERENCE) / (MAX_DISTANCE - REFERENCE)
+        }
+        DistanceModel::LinearClamped => {
+            1.0 - ROLLOFF * (clamped - REFERENCE) / (MAX_DISTANCE - REFERENCE)
+        }
+        DistanceModel::Exponent => (distance / REFERENCE).powf(-ROLLOFF),
+        DistanceModel::ExponentClamped => (clamped / REFERENCE).powf(-ROLLOFF),
+    };
+    if gain.is_finite() {
+        gain.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+pub(crate) fn sanitize_pitch(pitch: f32) -> f32 {
+    if pitch.is_finite() {
+        pitch.max(MIN_PITCH)
+    } else {
+        1.0
+    }
+}
+
+fn sanitize_gain(gain: f32) -> f32 {
+    if gain.is_finite() {
+        gain.max(0.0)
+    } else {
+        0.0
+    }
+}
+
 fn sanitize_vec3(v: Vec3) -> Vec3 {
     if v.is_finite() {
         v
@@ -681,9 +1877,17 @@ fn build_context_attributes(
     api: &OpenalApi,
     device: *mut ALCdevice,
     render_mode: AudioRenderMode,
+    max_sources: usize,
+    hrtf_id: Option<usize>,
+    output_mode: Option<ALCint>,
 ) -> Vec<ALCint> {
     let mut attrs: Vec<ALCint> = Vec::new();
 
+    if let Ok(mono_sources) = ALCint::try_from(max_sources) {
+        attrs.push(ALC_MONO_SOURCES);
+        attrs.push(mono_sources);
+    }
+
     let hrtf_ext = c"ALC_SOFT_HRTF";
     if render_mode == AudioRenderMode::HeadphonesHrtf && api.alc_has_extension(device, hrtf_ext) {
         let hrtf_key = api.alc_enum_value(device, cstr("ALC_HRTF_SOFT\0"));
@@ -691,35 +1895,20 @@ fn build_context_attributes(
             attrs.push(hrtf_key as ALCint);
             attrs.push(ALC_TRUE as ALCint);
         }
+        let id_key = api.alc_enum_value(device, c"ALC_HRTF_ID_SOFT");
+        if id_key != 0 {
+            if let Some(id) = hrtf_id.and_then(|id| ALCint::try_from(id).ok()) {
+                attrs.push(id_key as ALCint);
+                attrs.push(id);
+            }
+        }
     }
 
-    let output_ext = c"ALC_SOFT_output_mode";
-    if api.alc_has_extension(device, output_ext) {
+    if let Some(output_mode) = output_mode {
         let output_mode_key = api.alc_enum_value(device, cstr("ALC_OUTPUT_MODE_SOFT\0"));
         if output_mode_key != 0 {
-            match render_mode {
-                AudioRenderMode::StereoClean | AudioRenderMode::HeadphonesHrtf => {
-                    let stereo = api.alc_enum_value(device, cstr("ALC_STEREO_SOFT\0"));
-                    if stereo != 0 {
-                        attrs.push(output_mode_key as ALCint);
-                        attrs.push(stereo as ALCint);
-                    }
-                }
-                AudioRenderMode::SurroundAuto => {
-                    let any = api.alc_enum_value(device, cstr("ALC_ANY_SOFT\0"));
-                    if any != 0 {
-                        attrs.push(output_mode_key as ALCint);
-                        attrs.push(any as ALCint);
-                    }
-                }
-                AudioRenderMode::Auto => {
-                    let any = api.alc_enum_value(device, cstr("ALC_ANY_SOFT\0"));
-                    if any != 0 {
-                        attrs.push(output_mode_key as ALCint);
-                        attrs.push(any as ALCint);
-                    }
-                }
-            }
+            attrs.push(output_mode_key as ALCint);
+            attrs.push(output_mode);
         }
     }
 
@@ -727,6 +1916,62 @@ fn build_context_attributes(
     attrs
 }
 
+/// Returns the number of sources the context can actually play (`ALC_MONO_SOURCES` +
+/// `ALC_STEREO_SOURCES`), or `None` when the driver does not report it.
+fn query_device_source_limit(api: &OpenalApi, device: *mut ALCdevice) -> Option<usize> {
+    let mut mono: ALCint = 0;
+    let mut stereo: ALCint = 0;
+    unsafe {
+        (api.alc_get_integerv)(device, ALC_MONO_SOURCES, 1, &mut mono);
+        (api.alc_get_integerv)(device, ALC_STEREO_SOURCES, 1, &mut stereo);
+    }
+    if api
+        .check_alc(device, "alcGetIntegerv(ALC_MONO_SOURCES)")
+        .is_err()
+    {
+        return None;
+    }
+    let total = usize::try_from(mono.max(0)).ok()? + usize::try_from(stereo.max(0)).ok()?;
+    (total > 0).then_some(total)
+}
+
+fn query_output_sample_rate(api: &OpenalApi, device: *mut ALCdevice) -> Option<u32> {
+    let mut frequency: ALCint = 0;
+    unsafe { (api.alc_get_integerv)(device, ALC_FREQUENCY, 1, &mut frequency) };
+    if api
+        .check_alc(device, "alcGetIntegerv(ALC_FREQUENCY)")
+        .is_err()
+    {
+        return None;
+    }
+    u32::try_from(frequency).ok().filter(|&rate| rate > 0)
+}
+
+/// Generates up to `max_sources` sources (capped by the device limit), stopping early if the
+/// driver refuses to hand out more.
+fn allocate_source_pool(
+    api: &OpenalApi,
+    max_sources: usize,
+    device_limit: Option<usize>,
+) -> Vec<ALuint> {
+    let target = device_limit.map_or(max_sources, |limit| limit.min(max_sources));
+    let mut pool = Vec::with_capacity(target);
+    while pool.len() < target {
+        let mut source = 0;
+        unsafe { (api.al_gen_sources)(1, &mut source) };
+        if api.check_al("alGenSources(pool)").is_err() || source == 0 {
+            warn!(
+                requested = target,
+                allocated = pool.len(),
+                "OpenAL refused to allocate more sources"
+            );
+            break;
+        }
+        pool.push(source);
+    }
+    pool
+}
+
 fn query_hrtf_active(api: &OpenalApi, device: *mut ALCdevice) -> bool {
     let hrtf_ext = c"ALC_SOFT_HRTF";
     if !api.alc_has_extension(device, hrtf_ext) {
@@ -767,6 +2012,123 @@ fn query_hrtf_active(api: &OpenalApi, device: *mut ALCdevice) -> bool {
     value != 0 && (value == enabled || value == required || value == detected)
 }
 
+/// Names of the HRTF datasets `device` offers, in `ALC_HRTF_ID_SOFT` order.
+fn query_hrtf_datasets(api: &OpenalApi, device: *mut ALCdevice) -> Vec<String> {
+    if !api.alc_has_extension(device, c"ALC_SOFT_HRTF") {
+        return Vec::new();
+    }
+    let count_key = api.alc_enum_value(device, c"ALC_NUM_HRTF_SPECIFIERS_SOFT");
+    let specifier_key = api.alc_enum_value(device, c"ALC_HRTF_SPECIFIER_SOFT");
+    if count_key == 0 || specifier_key == 0 {
+        return Vec::new();
+    }
+    let proc = unsafe { (api.alc_get_proc_address)(device, c"alcGetStringiSOFT".as_ptr()) };
+    if proc.is_null() {
+        return Vec::new();
+    }
+    let get_string_i = unsafe { std::mem::transmute::<*mut c_void, AlcGetStringiSoft>(proc) };
+
+    let mut count: ALCint = 0;
+    unsafe { (api.alc_get_integerv)(device, count_key, 1, &mut count) };
+    if api
+        .check_alc(device, "alcGetIntegerv(ALC_NUM_HRTF_SPECIFIERS_SOFT)")
+        .is_err()
+    {
+        return Vec::new();
+    }
+    (0..count)
+        .filter_map(|index| {
+            let name = unsafe { get_string_i(device, specifier_key, index) };
+            (!name.is_null()).then(|| {
+                unsafe { CStr::from_ptr(name) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        })
+        .collect()
+}
+
+/// Name of the HRTF dataset the current context uses.
+fn query_hrtf_specifier(api: &OpenalApi, device: *mut ALCdevice) -> Option<String> {
+    let specifier_key = api.alc_enum_value(device, c"ALC_HRTF_SPECIFIER_SOFT");
+    if specifier_key == 0 {
+        return None;
+    }
+    let name = unsafe { (api.alc_get_string)(device, specifier_key) };
+    if name.is_null()
+        || api
+            .check_alc(device, "alcGetString(ALC_HRTF_SPECIFIER_SOFT)")
+            .is_err()
+    {
+        return None;
+    }
+    Some(
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// `ALC_OUTPUT_MODE_SOFT` values and the labels they are reported under.
+const OUTPUT_MODES: [(&str, &str); 11] = [
+    ("ALC_MONO_SOFT\0", "mono"),
+    ("ALC_STEREO_SOFT\0", "stereo"),
+    ("ALC_STEREO_BASIC_SOFT\0", "stereo-basic"),
+    ("ALC_STEREO_UHJ_SOFT\0", "stereo-uhj"),
+    ("ALC_STEREO_HRTF_SOFT\0", "stereo-hrtf"),
+    ("ALC_QUAD_SOFT\0", "quad"),
+    ("ALC_5POINT1_SOFT\0", "5.1"),
+    ("ALC_6POINT1_SOFT\0", "6.1"),
+    ("ALC_7POINT1_SOFT\0", "7.1"),
+    ("ALC_BFORMAT3D_SOFT\0", "bformat3d"),
+    ("ALC_ANY_SOFT\0", "auto"),
+];
+
+/// The output mode `render_mode` asks for, and the `ALC_OUTPUT_MODE_SOFT` values to try in
+/// fallback order (modes this OpenAL does not know are skipped). Layouts fall back to stereo and
+/// the automatic modes to whatever the device prefers. Empty without `ALC_SOFT_output_mode`.
+fn output_mode_requests(
+    api: &OpenalApi,
+    device: *mut ALCdevice,
+    render_mode: AudioRenderMode,
+) -> (Option<&'static str>, Vec<(&'static str, ALCint)>) {
+    if !api.alc_has_extension(device, c"ALC_SOFT_output_mode") {
+        return (None, Vec::new());
+    }
+    let labels = output_mode_labels(render_mode);
+    let requested = labels.first().copied();
+    let requests = labels
+        .into_iter()
+        .filter_map(|label| {
+            let (name, _) = OUTPUT_MODES.iter().find(|(_, known)| *known == label)?;
+            let value = api.alc_enum_value(device, cstr(name));
+            (value != 0).then_some((label, value as ALCint))
+        })
+        .collect();
+    (requested, requests)
+}
+
+/// Output modes requested for `render_mode`, most preferred first: the layout's fallbacks, then
+/// `auto` or `stereo`.
+fn output_mode_labels(render_mode: AudioRenderMode) -> Vec<&'static str> {
+    let layouts: &[OutputLayout] = match render_mode {
+        AudioRenderMode::Layout(layout) => layout.fallbacks(),
+        AudioRenderMode::SurroundAuto => &[OutputLayout::Surround71, OutputLayout::Surround51],
+        AudioRenderMode::Auto | AudioRenderMode::StereoClean | AudioRenderMode::HeadphonesHrtf => {
+            &[]
+        }
+    };
+    let last = match render_mode {
+        AudioRenderMode::Auto | AudioRenderMode::SurroundAuto => "auto",
+        _ => "stereo",
+    };
+    layouts
+        .iter()
+        .map(|layout| layout.as_str())
+        .chain(std::iter::once(last))
+        .collect()
+}
+
 fn query_output_mode(
     api: &OpenalApi,
     device: *mut ALCdevice,
@@ -790,20 +2152,6 @@ fn query_output_mode(
         return (None, None);
     }
 
-    const OUTPUT_MODES: [(&str, &str); 11] = [
-        ("ALC_MONO_SOFT\0", "mono"),
-        ("ALC_STEREO_SOFT\0", "stereo"),
-        ("ALC_STEREO_BASIC_SOFT\0", "stereo-basic"),
-        ("ALC_STEREO_UHJ_SOFT\0", "stereo-uhj"),
-        ("ALC_STEREO_HRTF_SOFT\0", "stereo-hrtf"),
-        ("ALC_QUAD_SOFT\0", "quad"),
-        ("ALC_5POINT1_SOFT\0", "5.1"),
-        ("ALC_6POINT1_SOFT\0", "6.1"),
-        ("ALC_7POINT1_SOFT\0", "7.1"),
-        ("ALC_BFORMAT3D_SOFT\0", "bformat3d"),
-        ("ALC_ANY_SOFT\0", "auto"),
-    ];
-
     let mut enum_values = Vec::new();
     for (enum_name, label) in OUTPUT_MODES {
         let mode = api.alc_enum_value(device, cstr(enum_name));
@@ -827,3 +2175,209 @@ fn query_output_mode(
 fn cstr(bytes: &'static str) -> &'static CStr {
     CStr::from_bytes_with_nul(bytes.as_bytes()).expect("CStr must be nul-terminated")
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{
+        distance_attenuation, hrtf_dataset_id, is_voice_finished, output_mode_labels,
+        pause_targets, plan_pool_resize, reattach_offset, steal_victim, ALenum, FadeStep, GainRamp,
+        PoolResize, Voice, AL_STOPPED, OUTPUT_MODES,
+    };
+    use crate::{
+        AudioBus, AudioRenderMode, DistanceModel, OutputLayout, PlayOneShotParams, VoiceFilter,
+    };
+
+    const AL_PLAYING: ALenum = 0x1012;
+    const AL_PAUSED: ALenum = 0x1013;
+
+    #[test]
+    fn gain_ramp_interpolates_and_clamps_at_target() {
+        let mut ramp = GainRamp::new(1.0, 0.0, Duration::from_millis(100));
+        ramp.elapsed = Duration::from_millis(25);
+        assert!((ramp.gain() - 0.75).abs() < 1e-6);
+        assert!(!ramp.is_finished());
+
+        ramp.elapsed = Duration::from_millis(130);
+        assert_eq!(ramp.gain(), 0.0);
+        assert!(ramp.is_finished());
+
+        assert_eq!(GainRamp::new(0.0, 0.5, Duration::ZERO).gain(), 0.5);
+    }
+
+    #[test]
+    fn gain_ramp_snapshot_reports_target_and_time_left() {
+        let mut ramp = GainRamp::new(1.0, 0.25, Duration::from_millis(100));
+        ramp.elapsed = Duration::from_millis(40);
+        let fade = ramp.snapshot();
+        assert_eq!(fade.target_gain, 0.25);
+        assert_eq!(fade.remaining, Duration::from_millis(60));
+
+        ramp.elapsed = Duration::from_millis(150);
+        assert_eq!(ramp.snapshot().remaining, Duration::ZERO);
+    }
+
+    #[test]
+    fn distance_attenuation_matches_openal_models() {
+        assert_eq!(distance_attenuation(DistanceModel::None, 50.0), 1.0);
+        assert_eq!(
+            distance_attenuation(DistanceModel::InverseClamped, 0.5),
+            1.0
+        );
+        assert!((distance_attenuation(DistanceModel::InverseClamped, 4.0) - 0.25).abs() < 1e-6);
+        assert!((distance_attenuation(DistanceModel::Inverse, 2.0) - 0.5).abs() < 1e-6);
+        assert!((distance_attenuation(DistanceModel::ExponentClamped, 10.0) - 0.1).abs() < 1e-6);
+        assert!(distance_attenuation(DistanceModel::LinearClamped, 10.0) > 0.99);
+    }
+
+    #[test]
+    fn reloading_a_paused_voice_keeps_it_alive() {
+        // A paused one-shot is stopped, rebound and left stopped at its offset.
+        assert_eq!(reattach_offset(false, AL_PAUSED, 300, 1_000), Some(300));
+        assert!(!is_voice_finished(true, AL_STOPPED));
+        assert!(is_voice_finished(false, AL_STOPPED));
+        assert!(!is_voice_finished(false, AL_PLAYING));
+
+        assert_eq!(reattach_offset(false, AL_PLAYING, 1_200, 1_000), None);
+        assert_eq!(reattach_offset(false, AL_STOPPED, 10, 1_000), None);
+        assert_eq!(reattach_offset(true, AL_PLAYING, 1_200, 1_000), Some(200));
+    }
+
+    #[test]
+    fn output_modes_fall_back_in_order() {
+        assert_eq!(
+            output_mode_labels(AudioRenderMode::Layout(OutputLayout::Surround71)),
+            ["7.1", "5.1", "quad", "stereo"]
+        );
+        assert_eq!(
+            output_mode_labels(AudioRenderMode::SurroundAuto),
+            ["7.1", "5.1", "auto"]
+        );
+        assert_eq!(
+            output_mode_labels(AudioRenderMode::Layout(OutputLayout::Mono)),
+            ["mono", "stereo"]
+        );
+        assert_eq!(output_mode_labels(AudioRenderMode::Auto), ["auto"]);
+        assert_eq!(
+            output_mode_labels(AudioRenderMode::HeadphonesHrtf),
+            ["stereo"]
+        );
+        for mode in [
+            AudioRenderMode::Layout(OutputLayout::Surround61),
+            AudioRenderMode::Layout(OutputLayout::StereoUhj),
+            AudioRenderMode::Layout(OutputLayout::Ambisonic3d),
+        ] {
+            for label in output_mode_labels(mode) {
+                assert!(
+                    OUTPUT_MODES.iter().any(|(_, known)| *known == label),
+                    "{label}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pool_resizes_in_place_until_the_context_limit() {
+        // Shrinking, and growing within the context's limit, happen in place.
+        assert_eq!(plan_pool_resize(16, Some(256)), PoolResize::InPlace(16));
+        assert_eq!(plan_pool_resize(256, Some(256)), PoolResize::InPlace(256));
+        assert_eq!(plan_pool_resize(128, None), PoolResize::InPlace(128));
+        // Growing past it needs a new context.
+        assert_eq!(plan_pool_resize(257, Some(256)), PoolResize::Reopen);
+    }
+
+    fn voice(id: u64, bus: AudioBus, paused: bool) -> Voice {
+        let params = PlayOneShotParams {
+            bus,
+            ..Default::default()
+        };
+        let mut voice = Voice::new(id, 1, id as u32, &params);
+        voice.paused = paused;
+        voice
+    }
+
+    #[test]
+    fn pause_targets_skip_voices_already_in_that_state() {
+        let voices = [
+            voice(1, AudioBus::Sfx, false),
+            voice(2, AudioBus::Music, true),
+            voice(3, AudioBus::Ui, false),
+        ];
+        let targets = |filter, paused| pause_targets(voices.iter(), filter, paused);
+
+        assert_eq!(targets(VoiceFilter::ExceptBus(AudioBus::Ui), true), [1]);
+        assert_eq!(targets(VoiceFilter::All, true), [1, 3]);
+        // Resuming only touches paused voices.
+        assert_eq!(targets(VoiceFilter::All, false), [2]);
+        assert!(targets(VoiceFilter::Bus(AudioBus::Sfx), false).is_empty());
+    }
+
+    #[test]
+    fn paused_voices_hold_their_fade() {
+        let params = PlayOneShotParams {
+            gain: 0.8,
+            fade_in: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let mut fading_in = Voice::new(1, 1, 1, &params);
+        fading_in.paused = true;
+        assert_eq!(
+            fading_in.advance_fade(Duration::from_millis(50)),
+            FadeStep::Idle
+        );
+        assert_eq!(fading_in.gain, 0.0);
+        fading_in.paused = false;
+        assert_eq!(
+            fading_in.advance_fade(Duration::from_millis(50)),
+            FadeStep::Gain
+        );
+        assert!((fading_in.gain - 0.4).abs() < 1e-6);
+
+        // A paused fade-out is not released until it resumes and completes.
+        let mut stopping = voice(2, AudioBus::Sfx, true);
+        stopping.stopping = true;
+        stopping.fade = Some(GainRamp::new(1.0, 0.0, Duration::from_millis(20)));
+        assert_eq!(
+            stopping.advance_fade(Duration::from_secs(1)),
+            FadeStep::Idle
+        );
+        stopping.paused = false;
+        assert_eq!(
+            stopping.advance_fade(Duration::from_secs(1)),
+            FadeStep::Stopped
+        );
+    }
+
+    #[test]
+    fn hrtf_datasets_resolve_by_name() {
+        let datasets = ["Built-In HRTF".to_string(), "Default HRTF".to_string()];
+        assert_eq!(hrtf_dataset_id(&datasets, "Default HRTF"), Some(1));
+        assert_eq!(hrtf_dataset_id(&datasets, "Built-In HRTF"), Some(0));
+        // Unknown names leave the choice to the device.
+        assert_eq!(hrtf_dataset_id(&datasets, "default hrtf"), None);
+        assert_eq!(hrtf_dataset_id(&[], "Default HRTF"), None);
+    }
+
+    #[test]
+    fn steals_fading_then_lowest_priority_then_oldest() {
+        let start = Instant::now();
+        let aged = |id: u64, priority: i32, age_ms: u64| {
+            let mut voice = voice(id, AudioBus::Sfx, false);
+            voice.priority = priority;
+            voice.started_at = start - Duration::from_millis(age_ms);
+            voice
+        };
+        let mut voices = vec![aged(1, 5, 300), aged(2, 1, 100), aged(3, 1, 200)];
+
+        // Lowest priority first, oldest among equals.
+        assert_eq!(steal_victim(&voices, 5), Some(2));
+        // Nothing above the new voice's priority is taken.
+        assert_eq!(steal_victim(&voices, 0), None);
+        voices.remove(2);
+        assert_eq!(steal_victim(&voices, 5), Some(1));
+        // A voice already fading out goes before anything else.
+        voices[0].stopping = true;
+        assert_eq!(steal_victim(&voices, 5), Some(0));
+    }
+}
@@ -1,26 +1,46 @@
 use glam::Vec3;
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::panic::{self, AssertUnwindSafe};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
-use crate::openal::{OpenalEngine, OpenalError};
-use crate::DecodedAudioMono16;
+use crate::command_queue::{Ack, CommandQueue, QueueError, QueuedCommand, Request};
+use crate::decode_worker::{DecodePool, ImportSource};
+use crate::openal::{sanitize_pitch, OpenalEngine, OpenalError, PoolResize};
+use crate::{AudioDecoder, DecodedAudioMono16, ImportOptions, SymphoniaDecoder};
 
 pub type BufferKey = u32;
 
+/// Identifies one playing instance of a buffer. Allocated by the runtime when a play command is
+/// submitted, so callers can correlate later [`AudioEvent`]s with the request.
+pub type VoiceId = u64;
+
+const EVENT_QUEUE_CAPACITY: usize = 256;
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+const VOICE_SNAPSHOT_INTERVAL: Duration = Duration::from_millis(100);
+const ERROR_HISTORY_CAPACITY: usize = 32;
+/// Fade applied by `StopLoop`; long enough to avoid a click, short enough to feel immediate.
+const STOP_LOOP_FADE: Duration = Duration::from_millis(20);
+/// Import rate used by [`ImportSampleRate::Device`] while no device is open (the content
+/// conditioning target).
+const DEFAULT_IMPORT_SAMPLE_RATE_HZ: u32 = 48_000;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AudioRenderMode {
     #[default]
     Auto,
     StereoClean,
     HeadphonesHrtf,
+    /// 7.1 when the device provides it, else 5.1, else whatever the device prefers.
     SurroundAuto,
+    /// An explicit speaker layout, falling back along [`OutputLayout::fallbacks`].
+    Layout(OutputLayout),
 }
 
 impl AudioRenderMode {
@@ -30,6 +50,7 @@ impl AudioRenderMode {
             AudioRenderMode::StereoClean => "stereo",
             AudioRenderMode::HeadphonesHrtf => "hrtf",
             AudioRenderMode::SurroundAuto => "surround",
+            AudioRenderMode::Layout(layout) => layout.as_str(),
         }
     }
 
@@ -39,12 +60,78 @@ impl AudioRenderMode {
             "stereo" | "stereo-clean" => Some(AudioRenderMode::StereoClean),
             "hrtf" | "headphones" => Some(AudioRenderMode::HeadphonesHrtf),
             "surround" | "surround-auto" => Some(AudioRenderMode::SurroundAuto),
+            other => OutputLayout::parse(other).map(AudioRenderMode::Layout),
+        }
+    }
+}
+
+/// Speaker layouts that can be requested through `ALC_OUTPUT_MODE_SOFT`. Plain stereo is
+/// [`AudioRenderMode::StereoClean`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputLayout {
+    Mono,
+    StereoUhj,
+    Quad,
+    Surround51,
+    Surround61,
+    Surround71,
+    /// 3D ambisonics (B-Format) for an external decoder. The ambisonic order is OpenAL Soft's
+    /// configured default: `ALC_AMBISONIC_ORDER_SOFT` only applies to loopback devices.
+    Ambisonic3d,
+}
+
+impl OutputLayout {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputLayout::Mono => "mono",
+            OutputLayout::StereoUhj => "stereo-uhj",
+            OutputLayout::Quad => "quad",
+            OutputLayout::Surround51 => "5.1",
+            OutputLayout::Surround61 => "6.1",
+            OutputLayout::Surround71 => "7.1",
+            OutputLayout::Ambisonic3d => "bformat3d",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "mono" => Some(OutputLayout::Mono),
+            "stereo-uhj" | "uhj" => Some(OutputLayout::StereoUhj),
+            "quad" => Some(OutputLayout::Quad),
+            "5.1" => Some(OutputLayout::Surround51),
+            "6.1" => Some(OutputLayout::Surround61),
+            "7.1" => Some(OutputLayout::Surround71),
+            "bformat3d" | "ambisonic" => Some(OutputLayout::Ambisonic3d),
             _ => None,
         }
     }
+
+    /// Layouts tried in order when this one is requested, starting with itself. If the device
+    /// provides none of them, the output opens as stereo.
+    pub fn fallbacks(self) -> &'static [OutputLayout] {
+        match self {
+            OutputLayout::Mono => &[OutputLayout::Mono],
+            OutputLayout::StereoUhj => &[OutputLayout::StereoUhj],
+            OutputLayout::Quad => &[OutputLayout::Quad],
+            OutputLayout::Surround51 => &[OutputLayout::Surround51, OutputLayout::Quad],
+            OutputLayout::Surround61 => &[
+                OutputLayout::Surround61,
+                OutputLayout::Surround51,
+                OutputLayout::Quad,
+            ],
+            OutputLayout::Surround71 => &[
+                OutputLayout::Surround71,
+                OutputLayout::Surround51,
+                OutputLayout::Quad,
+            ],
+            OutputLayout::Ambisonic3d => &[OutputLayout::Ambisonic3d],
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DistanceModel {
     #[default]
     None,
@@ -83,12 +170,151 @@ impl DistanceModel {
     }
 }
 
+/// Sample rate that imported PCM is resampled to before it becomes a buffer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImportSampleRate {
+    /// Keep the file's own rate; OpenAL resamples each voice while mixing.
+    Native,
+    /// Match the open device's mixing rate (`ALC_FREQUENCY`), or 48 kHz while no device is open.
+    #[default]
+    Device,
+    /// Always resample to this rate.
+    Fixed(u32),
+}
+
+/// Selects voices by bus for bulk commands such as pause-all.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum VoiceFilter {
+    #[default]
+    All,
+    Bus(AudioBus),
+    /// Every voice except those on this bus, e.g. pausing the world but not `Ui`.
+    ExceptBus(AudioBus),
+}
+
+impl VoiceFilter {
+    pub fn matches(self, bus: AudioBus) -> bool {
+        match self {
+            VoiceFilter::All => true,
+            VoiceFilter::Bus(only) => bus == only,
+            VoiceFilter::ExceptBus(excluded) => bus != excluded,
+        }
+    }
+}
+
+/// Mix group a voice belongs to. Used for filtering and reporting; routing and per-bus volume
+/// are applied by the runtime.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioBus {
+    #[default]
+    Sfx,
+    Ui,
+    Music,
+    Voice,
+    Ambience,
+}
+
+impl AudioBus {
+    pub const ALL: [AudioBus; 5] = [
+        AudioBus::Sfx,
+        AudioBus::Ui,
+        AudioBus::Music,
+        AudioBus::Voice,
+        AudioBus::Ambience,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AudioBus::Sfx => "sfx",
+            AudioBus::Ui => "ui",
+            AudioBus::Music => "music",
+            AudioBus::Voice => "vo",
+            AudioBus::Ambience => "ambience",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "sfx" => Some(AudioBus::Sfx),
+            "ui" => Some(AudioBus::Ui),
+            "music" => Some(AudioBus::Music),
+            "vo" | "voice" => Some(AudioBus::Voice),
+            "ambience" | "amb" => Some(AudioBus::Ambience),
+            _ => None,
+        }
+    }
+}
+
+/// Linear gain per [`AudioBus`], multiplied into every voice on the bus (1.0 = unchanged).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct BusVolumes {
+    pub sfx: f32,
+    pub ui: f32,
+    pub music: f32,
+    pub voice: f32,
+    pub ambience: f32,
+}
+
+impl Default for BusVolumes {
+    fn default() -> Self {
+        Self {
+            sfx: 1.0,
+            ui: 1.0,
+            music: 1.0,
+            voice: 1.0,
+            ambience: 1.0,
+        }
+    }
+}
+
+impl BusVolumes {
+    pub fn get(&self, bus: AudioBus) -> f32 {
+        match bus {
+            AudioBus::Sfx => self.sfx,
+            AudioBus::Ui => self.ui,
+            AudioBus::Music => self.music,
+            AudioBus::Voice => self.voice,
+            AudioBus::Ambience => self.ambience,
+        }
+    }
+
+    pub fn set(&mut self, bus: AudioBus, volume: f32) {
+        let slot = match bus {
+            AudioBus::Sfx => &mut self.sfx,
+            AudioBus::Ui => &mut self.ui,
+            AudioBus::Music => &mut self.music,
+            AudioBus::Voice => &mut self.voice,
+            AudioBus::Ambience => &mut self.ambience,
+        };
+        *slot = volume;
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AudioRuntimeConfig {
     pub initial_render_mode: AudioRenderMode,
     pub distance_model: DistanceModel,
     pub max_sources: usize,
     pub preferred_device: Option<String>,
+    /// HRTF dataset requested for [`AudioRenderMode::HeadphonesHrtf`], by name (see
+    /// [`AudioRuntimeStatus::hrtf_datasets`]); `None` or an unknown name uses the default.
+    pub hrtf_dataset: Option<String>,
+    /// Maximum number of pending non-coalesced commands before submission fails.
+    pub command_queue_capacity: usize,
+    /// Threads used by [`AudioRuntime::decode_buffer`]; at least one is always started.
+    pub decode_workers: usize,
+    /// Rate buffers are resampled to by `decode_buffer*` and `import_buffer*`, on the decode
+    /// pool. `create_buffer*` and `replace_buffer` upload PCM unchanged.
+    pub import_sample_rate: ImportSampleRate,
+    /// Decoder used by [`AudioRuntime::decode_buffer`].
+    pub decoder: Arc<dyn AudioDecoder>,
 }
 
 impl Default for AudioRuntimeConfig {
@@ -98,6 +324,11 @@ impl Default for AudioRuntimeConfig {
             distance_model: DistanceModel::InverseClamped,
             max_sources: 64,
             preferred_device: None,
+            hrtf_dataset: None,
+            command_queue_capacity: 1024,
+            decode_workers: 2,
+            import_sample_rate: ImportSampleRate::Device,
+            decoder: Arc::new(SymphoniaDecoder),
         }
     }
 }
@@ -108,25 +339,168 @@ pub struct AudioRuntimeStatus {
     pub device_open: bool,
     pub context_created: bool,
     pub render_mode: AudioRenderMode,
+    /// Output mode asked of the device for the render mode (`ALC_OUTPUT_MODE_SOFT`, e.g. "7.1").
+    pub output_mode_requested: Option<String>,
+    /// Output mode the device actually opened with; may be a fallback of the request.
     pub output_mode: Option<String>,
     pub output_mode_raw: Option<i32>,
+    /// Mixing rate of the open device (`ALC_FREQUENCY`).
+    pub device_sample_rate_hz: Option<u32>,
     pub distance_model: DistanceModel,
     pub hrtf_active: bool,
+    /// HRTF datasets the open device offers.
+    pub hrtf_datasets: Vec<String>,
+    /// Dataset in use while HRTF is active.
+    pub hrtf_dataset: Option<String>,
     pub muted: bool,
+    /// Listener gain while unmuted (linear).
+    pub master_volume: f32,
+    /// Pitch multiplier for every voice outside [`AudioBus::Ui`].
+    pub pitch_scale: f32,
+    /// Gain applied to each bus on top of the voice gain.
+    pub bus_volumes: BusVolumes,
+    /// Device mixing is suspended through `ALC_SOFT_pause_device`.
+    pub device_paused: bool,
     pub loaded_buffers: usize,
     pub active_sources: usize,
-    pub last_error: Option<String>,
+    /// Number of sources pre-allocated at device open (capped by `max_sources` and the device).
+    pub source_pool_size: usize,
+    /// Commands waiting for the audio thread.
+    pub command_queue_depth: usize,
+    /// Listener/voice transform updates replaced by a newer update before delivery.
+    pub commands_coalesced: u64,
+    /// Commands rejected because the queue was full.
+    pub commands_dropped: u64,
+    /// Lifecycle events discarded because the event queue was full (not drained in time).
+    pub events_dropped: u64,
+    /// Background decode jobs queued or in progress.
+    pub decodes_pending: usize,
+    /// Background decode jobs whose buffer was created.
+    pub decodes_completed: u64,
+    /// Background decode jobs that failed to decode or upload.
+    pub decodes_failed: u64,
+    /// Most recent errors, oldest first, capped at a fixed length.
+    pub recent_errors: VecDeque<AudioErrorRecord>,
+}
+
+impl AudioRuntimeStatus {
+    pub fn last_error(&self) -> Option<&AudioErrorRecord> {
+        self.recent_errors.back()
+    }
+
+    /// Resolves the rate imported buffers should be stored at, or `None` to keep their own.
+    pub(crate) fn import_rate_hz(&self, policy: ImportSampleRate) -> Option<u32> {
+        match policy {
+            ImportSampleRate::Native => None,
+            ImportSampleRate::Device => Some(
+                self.device_sample_rate_hz
+                    .unwrap_or(DEFAULT_IMPORT_SAMPLE_RATE_HZ),
+            ),
+            ImportSampleRate::Fixed(rate) => Some(rate),
+        }
+    }
+
+    /// Clears everything that describes an open device, keeping the mix and error history.
+    pub(crate) fn record_device_closed(&mut self) {
+        self.device_open = false;
+        self.context_created = false;
+        self.output_mode_requested = None;
+        self.output_mode = None;
+        self.output_mode_raw = None;
+        self.device_sample_rate_hz = None;
+        self.hrtf_active = false;
+        self.hrtf_datasets.clear();
+        self.hrtf_dataset = None;
+        self.loaded_buffers = 0;
+        self.active_sources = 0;
+        self.source_pool_size = 0;
+    }
+
+    pub(crate) fn record_error(&mut self, error: RuntimeError) {
+        while self.recent_errors.len() >= ERROR_HISTORY_CAPACITY {
+            self.recent_errors.pop_front();
+        }
+        self.recent_errors.push_back(AudioErrorRecord {
+            at: SystemTime::now(),
+            error,
+        });
+    }
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone)]
+pub struct AudioErrorRecord {
+    pub at: SystemTime,
+    pub error: RuntimeError,
+}
+
+#[derive(Debug, Clone, Error)]
 pub enum RuntimeError {
     #[error("audio runtime is not available")]
     NotAvailable,
     #[error("audio runtime thread stopped unexpectedly")]
     ThreadStopped,
+    #[error("audio command queue is full")]
+    QueueFull,
+    #[error(transparent)]
+    Openal(#[from] OpenalError),
+    #[error("audio runtime panic: {0}")]
+    Panicked(String),
+    #[error("audio device disconnected")]
+    DeviceDisconnected,
+    #[error("failed to decode audio for buffer {key}: {reason}")]
+    DecodeFailed { key: BufferKey, reason: String },
 }
 
-#[derive(Debug, Copy, Clone)]
+/// Playback lifecycle notifications sent from the audio thread back to the owner of the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioEvent {
+    VoiceStarted {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    VoiceFinished {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    /// The voice was stopped early to free a source for a newer voice.
+    VoiceStolen {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    /// The one-shot was cut because the output was reopened (device, HRTF dataset or render mode
+    /// change, or `max_sources` above the context's limit). Paused one-shots are dropped too and
+    /// only reported here. The loop is restarted instead, paused again if it was paused.
+    VoiceDropped {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    VoicePaused {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    VoiceResumed {
+        voice: VoiceId,
+        key: BufferKey,
+    },
+    PlayFailed {
+        voice: VoiceId,
+        key: BufferKey,
+        reason: String,
+    },
+    /// The output device was disconnected; a render mode change reopens it.
+    DeviceLost,
+    /// A background decode job finished and its buffer was created.
+    BufferReady {
+        key: BufferKey,
+    },
+    /// A background decode job failed to decode or upload its buffer.
+    BufferFailed {
+        key: BufferKey,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ListenerFrame {
     pub position: Vec3,
     pub forward: Vec3,
@@ -150,6 +524,16 @@ pub struct PlayOneShotParams {
     pub position: Vec3,
     pub gain: f32,
     pub pitch: f32,
+    pub bus: AudioBus,
+    /// Higher values win when the source pool is exhausted; a new voice only steals from voices
+    /// with equal or lower priority.
+    pub priority: i32,
+    /// Ramps the voice up from silence to `gain`. For loops this also fades out the loop being
+    /// replaced.
+    pub fade_in: Duration,
+    /// Loops a one-shot until it is stopped with `stop_voice`. Unlike `start_loop`, any number of
+    /// these can play at once; they can be stolen like other one-shots.
+    pub looping: bool,
 }
 
 impl Default for PlayOneShotParams {
@@ -158,144 +542,663 @@ impl Default for PlayOneShotParams {
             position: Vec3::ZERO,
             gain: 1.0,
             pitch: 1.0,
+            bus: AudioBus::Sfx,
+            priority: 0,
+            fade_in: Duration::ZERO,
+            looping: false,
         }
     }
 }
 
-enum AudioCommand {
-    Shutdown,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum VoiceState {
+    Playing,
+    /// Paused with `alSourcePause`; keeps its source and offset.
+    Paused,
+    /// Fading out; the source is released once the fade completes.
+    Stopping,
+    /// Still holds a source but is attenuated below audibility at the listener.
+    Virtual,
+}
+
+impl VoiceState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VoiceState::Playing => "playing",
+            VoiceState::Paused => "paused",
+            VoiceState::Stopping => "stopping",
+            VoiceState::Virtual => "virtual",
+        }
+    }
+}
+
+/// Debug view of one voice, as of the latest snapshot.
+#[derive(Debug, Clone)]
+pub struct VoiceSnapshot {
+    pub voice: VoiceId,
+    pub key: BufferKey,
+    pub bus: AudioBus,
+    pub looping: bool,
+    pub position: Vec3,
+    pub distance: f32,
+    /// Distance attenuation from the active distance model (0..=1), excluding voice gain.
+    pub attenuation: f32,
+    pub gain: f32,
+    pub priority: i32,
+    pub state: VoiceState,
+    /// Gain ramp in progress (fade-in, `fade_to` or fade-out on stop).
+    pub fade: Option<VoiceFade>,
+    pub age: Duration,
+    pub offset: VoiceOffset,
+}
+
+/// A gain fade running on a voice, as of the latest snapshot.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VoiceFade {
+    /// Gain the fade ends at; `gain` is the current point on the ramp.
+    pub target_gain: f32,
+    pub remaining: Duration,
+}
+
+/// Playback position of a voice within its buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct VoiceOffset {
+    /// Audible position in seconds. Latency-compensated when the device supports
+    /// `AL_SOFT_source_latency`.
+    pub seconds: f64,
+    /// Audible position in sample frames of the buffer.
+    pub sample: u64,
+    /// Output latency subtracted from the mixed position, when reported by the device.
+    pub latency_seconds: Option<f64>,
+    /// Length of the voice's buffer in seconds.
+    pub length_seconds: f64,
+}
+
+/// Seek target for [`AudioCommand::SetVoiceOffset`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlaybackOffset {
+    /// `AL_SEC_OFFSET`.
+    Seconds(f32),
+    /// `AL_SAMPLE_OFFSET`, in sample frames.
+    Samples(u32),
+}
+
+/// Periodic per-voice snapshot for debug HUDs. Published by the audio thread a few times per
+/// second and shared behind an `Arc`, so reading it never copies the voice list.
+#[derive(Debug, Clone, Default)]
+pub struct AudioVoiceSnapshot {
+    pub listener_position: Vec3,
+    pub voices: Vec<VoiceSnapshot>,
+}
+
+/// A request for the audio thread, submitted through [`AudioHandle::submit`].
+///
+/// `SetListener` and `SetVoiceTransform` are coalesced while pending: only the latest listener
+/// frame and the latest transform per voice are delivered.
+#[derive(Debug)]
+pub enum AudioCommand {
+    /// Silences the output without touching the master volume, so unmuting restores it.
     SetMuted(bool),
+    /// Listener gain (linear; see [`gain_from_db`]).
+    SetMasterVolume(f32),
+    /// Pitch (time-scale) multiplier for every voice outside [`AudioBus::Ui`], e.g. for slow
+    /// motion.
+    SetPitchScale(f32),
+    /// Linear gain of one [`AudioBus`], applied to its playing and future voices.
+    SetBusVolume {
+        bus: AudioBus,
+        volume: f32,
+    },
     SetRenderMode(AudioRenderMode),
     SetDistanceModel(DistanceModel),
+    /// Resizes the source pool. Shrinking steals one-shots; growing past the context's source
+    /// limit reopens the device.
+    SetMaxSources(usize),
+    /// Reopens the output on this device (`None` = system default).
+    SetPreferredDevice(Option<String>),
+    /// Selects an HRTF dataset by name (`None` = default), reopening the device.
+    SetHrtfDataset(Option<String>),
     SetListener(ListenerFrame),
+    SetVoiceTransform {
+        voice: VoiceId,
+        position: Vec3,
+        velocity: Vec3,
+    },
     CreateBuffer {
         key: BufferKey,
         decoded: DecodedAudioMono16,
     },
+    /// Swaps the audio of buffer `key` (hot reload), creating it if missing. Voices using it are
+    /// reattached and keep playing from the same offset.
+    ReplaceBuffer {
+        key: BufferKey,
+        decoded: DecodedAudioMono16,
+    },
+    /// Deletes buffer `key`; voices still playing it finish immediately.
+    DeleteBuffer {
+        key: BufferKey,
+    },
     PlayOneShot {
+        voice: VoiceId,
         key: BufferKey,
         params: PlayOneShotParams,
     },
     StartLoop {
+        voice: VoiceId,
         key: BufferKey,
         params: PlayOneShotParams,
     },
+    /// Stops the active loop with a short de-click fade.
     StopLoop,
+    /// Fades `voice` out over `fade_out`, then releases its source.
+    StopVoice {
+        voice: VoiceId,
+        fade_out: Duration,
+    },
+    /// Ramps a voice's gain to `gain` over `duration`.
+    FadeVoice {
+        voice: VoiceId,
+        gain: f32,
+        duration: Duration,
+    },
+    /// Seeks a playing voice.
+    SetVoiceOffset {
+        voice: VoiceId,
+        offset: PlaybackOffset,
+    },
+    PauseVoice {
+        voice: VoiceId,
+    },
+    ResumeVoice {
+        voice: VoiceId,
+    },
+    /// Pauses every playing voice matched by the filter. Voices started afterwards play normally.
+    PauseVoices(VoiceFilter),
+    ResumeVoices(VoiceFilter),
+    /// Suspends or resumes the whole output device (`ALC_SOFT_pause_device`).
+    SetDevicePaused(bool),
+}
+
+/// Value produced by a command, delivered to its ticket.
+pub(crate) enum CommandOutput {
+    Done,
+    Offset(VoiceOffset),
+}
+
+/// Cheap, cloneable submission handle for the audio thread. Submission never blocks: spammy
+/// updates are coalesced and other commands are rejected with [`RuntimeError::QueueFull`] once
+/// the queue is at capacity.
+#[derive(Clone)]
+pub struct AudioHandle {
+    queue: Arc<CommandQueue>,
+    next_voice: Arc<AtomicU64>,
+}
+
+impl AudioHandle {
+    pub fn submit(&self, command: AudioCommand) -> Result<(), RuntimeError> {
+        self.queue.push(command).map_err(|err| match err {
+            QueueError::Full => RuntimeError::QueueFull,
+            QueueError::Closed => RuntimeError::ThreadStopped,
+        })
+    }
+
+    /// Queues `command` and returns a ticket that resolves once the audio thread has applied it.
+    ///
+    /// Acknowledged commands are never coalesced, so acked listener/transform updates each take a
+    /// queue slot.
+    pub fn submit_acked(&self, command: AudioCommand) -> Result<AudioTicket, RuntimeError> {
+        self.submit_with_reply(Request::Command(command), |_| Ok(()))
+    }
+
+    fn submit_with_reply<T: Send + 'static>(
+        &self,
+        request: Request,
+        reply: fn(CommandOutput) -> Result<T, RuntimeError>,
+    ) -> Result<AudioTicket<T>, RuntimeError> {
+        let (sender, completion) = mpsc::sync_channel(1);
+        let ack: Ack = Box::new(move |result| {
+            let _ = sender.try_send(result.and_then(reply));
+        });
+        self.queue
+            .push_acked(request, ack)
+            .map_err(|err| match err {
+                QueueError::Full => RuntimeError::QueueFull,
+                QueueError::Closed => RuntimeError::ThreadStopped,
+            })?;
+        Ok(AudioTicket { completion })
+    }
+
+    /// Reserves a fresh [`VoiceId`] for a `PlayOneShot`/`StartLoop` command.
+    pub fn allocate_voice_id(&self) -> VoiceId {
+        self.next_voice.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// Completion token for a command submitted with [`AudioHandle::submit_acked`].
+///
+/// The result is delivered once. If the audio thread stops before running the command, the
+/// ticket resolves to [`RuntimeError::ThreadStopped`].
+#[must_use = "a ticket carries the command's result"]
+#[derive(Debug)]
+pub struct AudioTicket<T = ()> {
+    completion: mpsc::Receiver<Result<T, RuntimeError>>,
+}
+
+impl<T> AudioTicket<T> {
+    /// Returns the result if the command has completed, or `None` while it is still pending.
+    pub fn try_result(&self) -> Option<Result<T, RuntimeError>> {
+        match self.completion.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => Some(Err(RuntimeError::ThreadStopped)),
+        }
+    }
+
+    /// Blocks until the command has completed.
+    pub fn wait(self) -> Result<T, RuntimeError> {
+        self.completion
+            .recv()
+            .unwrap_or(Err(RuntimeError::ThreadStopped))
+    }
+
+    /// Blocks for at most `timeout`; returns `None` if the command is still pending.
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<Result<T, RuntimeError>> {
+        match self.completion.recv_timeout(timeout) {
+            Ok(result) => Some(result),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => Some(Err(RuntimeError::ThreadStopped)),
+        }
+    }
 }
 
 pub struct AudioRuntime {
-    tx: mpsc::Sender<AudioCommand>,
+    handle: AudioHandle,
+    events: Mutex<mpsc::Receiver<AudioEvent>>,
     status: Arc<Mutex<AudioRuntimeStatus>>,
+    voices: Arc<Mutex<Arc<AudioVoiceSnapshot>>>,
+    decoder: DecodePool,
     thread: Option<thread::JoinHandle<()>>,
     shutdown_requested: AtomicBool,
 }
 
 impl AudioRuntime {
     pub fn new(config: AudioRuntimeConfig) -> Result<Self, RuntimeError> {
-        let (tx, rx) = mpsc::channel::<AudioCommand>();
+        let queue = Arc::new(CommandQueue::new(config.command_queue_capacity));
+        let (event_tx, event_rx) = mpsc::sync_channel::<AudioEvent>(EVENT_QUEUE_CAPACITY);
         let status = Arc::new(Mutex::new(AudioRuntimeStatus {
             render_mode: config.initial_render_mode,
             distance_model: config.distance_model,
+            master_volume: 1.0,
+            pitch_scale: 1.0,
             ..Default::default()
         }));
 
+        let voices = Arc::new(Mutex::new(Arc::new(AudioVoiceSnapshot::default())));
+
+        let decode_workers = config.decode_workers;
+        let import_sample_rate = config.import_sample_rate;
+        let decoder = Arc::clone(&config.decoder);
+        let decode_events = event_tx.clone();
         let thread_status = Arc::clone(&status);
+        let thread_voices = Arc::clone(&voices);
+        let thread_queue = Arc::clone(&queue);
         let thread = thread::Builder::new()
             .name("zrg-audio".to_string())
             .spawn(move || {
                 let status_for_panic = Arc::clone(&thread_status);
+                let queue_for_exit = Arc::clone(&thread_queue);
                 let result = panic::catch_unwind(AssertUnwindSafe(|| {
-                    audio_thread_main(config, rx, thread_status);
+                    audio_thread_main(
+                        config,
+                        &thread_queue,
+                        event_tx,
+                        thread_status,
+                        thread_voices,
+                    );
                 }));
+                queue_for_exit.close();
                 if let Err(panic) = result {
                     let message = panic_message(panic);
-                    if let Ok(mut st) = status_for_panic.lock() {
-                        st.last_error = Some(format!("audio runtime panic: {message}"));
-                    }
+                    record_error(&status_for_panic, RuntimeError::Panicked(message.clone()));
                     error!(panic = %message, "Audio runtime thread panicked");
                 }
             })
             .map_err(|_| RuntimeError::NotAvailable)?;
 
+        let handle = AudioHandle {
+            queue,
+            next_voice: Arc::new(AtomicU64::new(1)),
+        };
+        let decoder = DecodePool::new(
+            decode_workers,
+            decoder,
+            import_sample_rate,
+            handle.clone(),
+            decode_events,
+            Arc::clone(&status),
+        );
+
         Ok(Self {
-            tx,
+            handle,
+            events: Mutex::new(event_rx),
             status,
+            voices,
+            decoder,
             thread: Some(thread),
             shutdown_requested: AtomicBool::new(false),
         })
     }
 
+    /// Returns a submission handle that can be cloned and moved to other threads.
+    pub fn handle(&self) -> AudioHandle {
+        self.handle.clone()
+    }
+
+    /// Returns every lifecycle event received since the previous call. Events are buffered up to
+    /// a fixed capacity; once it is full, new events are dropped (counted in
+    /// [`AudioRuntimeStatus::events_dropped`]) rather than blocking the audio thread.
+    pub fn drain_events(&self) -> Vec<AudioEvent> {
+        let Ok(events) = self.events.lock() else {
+            return Vec::new();
+        };
+        events.try_iter().collect()
+    }
+
     pub fn status(&self) -> AudioRuntimeStatus {
         self.status.lock().map(|s| s.clone()).unwrap_or_default()
     }
 
+    /// Returns the latest published voice snapshot. Cheap: clones an `Arc`.
+    pub fn voice_snapshot(&self) -> Arc<AudioVoiceSnapshot> {
+        self.voices
+            .lock()
+            .map(|snapshot| Arc::clone(&snapshot))
+            .unwrap_or_default()
+    }
+
     pub fn is_shutdown_requested(&self) -> bool {
         self.shutdown_requested.load(Ordering::Relaxed)
     }
 
     pub fn set_render_mode(&self, mode: AudioRenderMode) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::SetRenderMode(mode))
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle.submit(AudioCommand::SetRenderMode(mode))
     }
 
     pub fn set_distance_model(&self, model: DistanceModel) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::SetDistanceModel(model))
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle.submit(AudioCommand::SetDistanceModel(model))
     }
 
     pub fn set_muted(&self, muted: bool) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::SetMuted(muted))
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle.submit(AudioCommand::SetMuted(muted))
+    }
+
+    pub fn set_master_volume(&self, volume: f32) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetMasterVolume(volume))
+    }
+
+    pub fn set_pitch_scale(&self, scale: f32) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetPitchScale(scale))
+    }
+
+    pub fn set_bus_volume(&self, bus: AudioBus, volume: f32) -> Result<(), RuntimeError> {
+        self.handle
+            .submit(AudioCommand::SetBusVolume { bus, volume })
+    }
+
+    pub fn set_max_sources(&self, max_sources: usize) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetMaxSources(max_sources))
+    }
+
+    pub fn set_preferred_device(&self, device: Option<String>) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetPreferredDevice(device))
+    }
+
+    pub fn set_hrtf_dataset(&self, dataset: Option<String>) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetHrtfDataset(dataset))
     }
 
     pub fn set_listener(&self, listener: ListenerFrame) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::SetListener(listener))
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle.submit(AudioCommand::SetListener(listener))
     }
 
+    pub fn set_voice_transform(
+        &self,
+        voice: VoiceId,
+        position: Vec3,
+        velocity: Vec3,
+    ) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetVoiceTransform {
+            voice,
+            position,
+            velocity,
+        })
+    }
+
+    /// Queues `decoded` as buffer `key` at its own sample rate; nothing heavy runs on the calling
+    /// thread. Use [`Self::import_buffer`] to resample to `import_sample_rate` off-thread.
     pub fn create_buffer(
         &self,
         key: BufferKey,
         decoded: DecodedAudioMono16,
     ) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::CreateBuffer { key, decoded })
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle
+            .submit(AudioCommand::CreateBuffer { key, decoded })
+    }
+
+    /// Like [`Self::create_buffer`], but the ticket reports whether the buffer was actually
+    /// created (e.g. `BufferKeyExists`, `BufferDataTooLarge`).
+    pub fn create_buffer_acked(
+        &self,
+        key: BufferKey,
+        decoded: DecodedAudioMono16,
+    ) -> Result<AudioTicket, RuntimeError> {
+        self.handle
+            .submit_acked(AudioCommand::CreateBuffer { key, decoded })
+    }
+
+    /// Replaces the audio of buffer `key` with `decoded`, e.g. after the source file changed.
+    /// Loops and one-shots using the buffer continue with the new data.
+    pub fn replace_buffer(
+        &self,
+        key: BufferKey,
+        decoded: DecodedAudioMono16,
+    ) -> Result<(), RuntimeError> {
+        self.handle
+            .submit(AudioCommand::ReplaceBuffer { key, decoded })
+    }
+
+    /// Frees buffer `key`. Voices playing it are finished (`VoiceFinished`), and the buffer is not
+    /// recreated when the device is rebuilt.
+    pub fn delete_buffer(&self, key: BufferKey) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::DeleteBuffer { key })
+    }
+
+    /// Decodes `bytes` on the background decode pool and creates buffer `key` from the result.
+    ///
+    /// Returns once the job is queued. Completion is reported as `AudioEvent::BufferReady` or
+    /// `AudioEvent::BufferFailed`, and counted in the `decodes_*` status fields.
+    pub fn decode_buffer(&self, key: BufferKey, bytes: Vec<u8>) -> Result<(), RuntimeError> {
+        self.decode_buffer_with(key, bytes, ImportOptions::default())
+    }
+
+    /// Like [`Self::decode_buffer`], with per-buffer import conditioning.
+    pub fn decode_buffer_with(
+        &self,
+        key: BufferKey,
+        bytes: Vec<u8>,
+        options: ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        self.decoder
+            .submit(key, ImportSource::Encoded(bytes), false, options)
+    }
+
+    /// Like [`Self::decode_buffer`], but replaces an existing buffer `key` as
+    /// [`Self::replace_buffer`] does.
+    pub fn decode_replace_buffer(
+        &self,
+        key: BufferKey,
+        bytes: Vec<u8>,
+    ) -> Result<(), RuntimeError> {
+        self.decode_replace_buffer_with(key, bytes, ImportOptions::default())
+    }
+
+    /// Like [`Self::decode_replace_buffer`], with per-buffer import conditioning.
+    pub fn decode_replace_buffer_with(
+        &self,
+        key: BufferKey,
+        bytes: Vec<u8>,
+        options: ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        self.decoder
+            .submit(key, ImportSource::Encoded(bytes), true, options)
+    }
+
+    /// Conditions and resamples already decoded PCM on the background decode pool, then creates
+    /// buffer `key`. Completion is reported like [`Self::decode_buffer`].
+    pub fn import_buffer(
+        &self,
+        key: BufferKey,
+        decoded: DecodedAudioMono16,
+        options: ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        self.decoder
+            .submit(key, ImportSource::Pcm(decoded), false, options)
+    }
+
+    /// Like [`Self::import_buffer`], but replaces an existing buffer `key` as
+    /// [`Self::replace_buffer`] does.
+    pub fn import_replace_buffer(
+        &self,
+        key: BufferKey,
+        decoded: DecodedAudioMono16,
+        options: ImportOptions,
+    ) -> Result<(), RuntimeError> {
+        self.decoder
+            .submit(key, ImportSource::Pcm(decoded), true, options)
     }
 
     pub fn play_one_shot(
         &self,
         key: BufferKey,
         params: PlayOneShotParams,
-    ) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::PlayOneShot { key, params })
-            .map_err(|_| RuntimeError::ThreadStopped)
+    ) -> Result<VoiceId, RuntimeError> {
+        let voice = self.handle.allocate_voice_id();
+        self.handle
+            .submit(AudioCommand::PlayOneShot { voice, key, params })?;
+        Ok(voice)
+    }
+
+    /// Like [`Self::play_one_shot`], but the ticket reports whether the voice started.
+    pub fn play_one_shot_acked(
+        &self,
+        key: BufferKey,
+        params: PlayOneShotParams,
+    ) -> Result<(VoiceId, AudioTicket), RuntimeError> {
+        let voice = self.handle.allocate_voice_id();
+        let ticket = self
+            .handle
+            .submit_acked(AudioCommand::PlayOneShot { voice, key, params })?;
+        Ok((voice, ticket))
     }
 
     pub fn start_loop(
         &self,
         key: BufferKey,
         params: PlayOneShotParams,
+    ) -> Result<VoiceId, RuntimeError> {
+        let voice = self.handle.allocate_voice_id();
+        self.handle
+            .submit(AudioCommand::StartLoop { voice, key, params })?;
+        Ok(voice)
+    }
+
+    /// Like [`Self::start_loop`], but the ticket reports whether the loop started.
+    pub fn start_loop_acked(
+        &self,
+        key: BufferKey,
+        params: PlayOneShotParams,
+    ) -> Result<(VoiceId, AudioTicket), RuntimeError> {
+        let voice = self.handle.allocate_voice_id();
+        let ticket = self
+            .handle
+            .submit_acked(AudioCommand::StartLoop { voice, key, params })?;
+        Ok((voice, ticket))
+    }
+
+    /// Seeks `voice`. Out-of-range offsets fail on the audio thread and are recorded in the status.
+    pub fn set_voice_offset(
+        &self,
+        voice: VoiceId,
+        offset: PlaybackOffset,
     ) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::StartLoop { key, params })
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle
+            .submit(AudioCommand::SetVoiceOffset { voice, offset })
+    }
+
+    /// Reads the current playback offset of `voice`. For periodic UI, the offsets in
+    /// [`Self::voice_snapshot`] avoid a round trip.
+    pub fn voice_offset(&self, voice: VoiceId) -> Result<AudioTicket<VoiceOffset>, RuntimeError> {
+        self.handle
+            .submit_with_reply(Request::VoiceOffset(voice), |output| match output {
+                CommandOutput::Offset(offset) => Ok(offset),
+                CommandOutput::Done => Err(RuntimeError::NotAvailable),
+            })
+    }
+
+    pub fn pause_voice(&self, voice: VoiceId) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::PauseVoice { voice })
+    }
+
+    pub fn resume_voice(&self, voice: VoiceId) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::ResumeVoice { voice })
     }
 
+    /// Pauses the voices matched by `filter`; e.g. `VoiceFilter::ExceptBus(AudioBus::Ui)` for a
+    /// pause menu that keeps UI sounds running.
+    pub fn pause_all(&self, filter: VoiceFilter) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::PauseVoices(filter))
+    }
+
+    pub fn resume_all(&self, filter: VoiceFilter) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::ResumeVoices(filter))
+    }
+
+    /// Suspends device mixing, e.g. while the app is in the background. Fails on the audio
+    /// thread with `ExtensionUnavailable` when the device lacks `ALC_SOFT_pause_device`.
+    pub fn set_device_paused(&self, paused: bool) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::SetDevicePaused(paused))
+    }
+
+    /// Stops the active loop after a 20 ms fade so it does not click.
+    /// Use [`Self::stop_voice`] with the loop's voice id for a longer fade.
     pub fn stop_loop(&self) -> Result<(), RuntimeError> {
-        self.tx
-            .send(AudioCommand::StopLoop)
-            .map_err(|_| RuntimeError::ThreadStopped)
+        self.handle.submit(AudioCommand::StopLoop)
+    }
+
+    /// Fades `voice` out over `fade_out` and then stops it; `Duration::ZERO` stops immediately.
+    pub fn stop_voice(&self, voice: VoiceId, fade_out: Duration) -> Result<(), RuntimeError> {
+        self.handle
+            .submit(AudioCommand::StopVoice { voice, fade_out })
+    }
+
+    /// Ramps the gain of `voice` to `gain` over `duration`.
+    pub fn fade_to(
+        &self,
+        voice: VoiceId,
+        gain: f32,
+        duration: Duration,
+    ) -> Result<(), RuntimeError> {
+        self.handle.submit(AudioCommand::FadeVoice {
+            voice,
+            gain,
+            duration,
+        })
     }
 
     pub fn shutdown(&mut self) {
         self.shutdown_requested.store(true, Ordering::Relaxed);
-        let _ = self.tx.send(AudioCommand::Shutdown);
+        self.handle.queue.close();
+        self.decoder.shutdown();
         if let Some(thread) = self.thread.take() {
             let _ = thread.join();
         }
@@ -310,271 +1213,601 @@ impl Drop for AudioRuntime {
 
 fn audio_thread_main(
     config: AudioRuntimeConfig,
-    rx: mpsc::Receiver<AudioCommand>,
+    queue: &CommandQueue,
+    events: mpsc::SyncSender<AudioEvent>,
     status: Arc<Mutex<AudioRuntimeStatus>>,
+    voices: Arc<Mutex<Arc<AudioVoiceSnapshot>>>,
 ) {
-    let mut render_mode = config.initial_render_mode;
-    let mut muted = false;
-    let mut distance_model = config.distance_model;
-    let mut buffers: HashMap<BufferKey, DecodedAudioMono16> = HashMap::new();
-    let mut loop_state: Option<(BufferKey, PlayOneShotParams)> = None;
-
-    let preferred_device = config.preferred_device.as_deref();
-
-    let mut engine = match panic::catch_unwind(AssertUnwindSafe(|| {
-        OpenalEngine::new(
-            render_mode,
-            preferred_device,
-            config.max_sources,
-            distance_model,
-        )
-    })) {
-        Ok(Ok(engine)) => {
-            let mut engine = engine;
-            rebuild_buffers(
-                &mut engine,
-                &buffers,
-                &status,
-                render_mode,
-                distance_model,
-                muted,
-            );
-            rebuild_loop(
-                &mut engine,
-                &loop_state,
-                &status,
-                render_mode,
-                distance_model,
-                muted,
-            );
-            update_status_ok(&status, render_mode, muted, &engine);
-            info!(render_mode = %render_mode.as_str(), "Audio runtime started");
-            Some(engine)
-        }
-        Ok(Err(err)) => {
-            update_status_error(&status, render_mode, distance_model, muted, &err);
-            error!(error = %err, "Audio runtime failed to initialize");
-            None
-        }
-        Err(panic) => {
-            let message = panic_message(panic);
-            update_status_panic(&status, render_mode, distance_model, muted, &message);
-            error!(panic = %message, "Audio runtime panicked during initialization");
-            None
-        }
+    let mut state = AudioThreadState {
+        render_mode: config.initial_render_mode,
+        mix: MasterMix::default(),
+        device_paused: false,
+        distance_model: config.distance_model,
+        buffers: HashMap::new(),
+        loop_state: None,
+        last_listener: ListenerFrame::default(),
+        device_lost: false,
+        engine: None,
+        config,
+        events,
+        status,
     };
+    // Failures are already recorded in the status; the runtime keeps accepting commands.
+    let _ = state.start_engine("Audio runtime panicked during initialization");
 
-    let mut last_listener = ListenerFrame::default();
+    let mut last_device_check = Instant::now();
+    let mut last_voice_snapshot = Instant::now();
 
     loop {
-        if let Some(engine) = engine.as_mut() {
+        if let Some(engine) = state.engine.as_mut() {
+            engine.update_fades();
             engine.cleanup_finished_sources();
-            update_counts(&status, engine);
+            update_counts(&state.status, engine);
+
+            if !state.device_lost && last_device_check.elapsed() >= DEVICE_CHECK_INTERVAL {
+                last_device_check = Instant::now();
+                if !engine.is_device_connected() {
+                    state.device_lost = true;
+                    if let Ok(mut st) = state.status.lock() {
+                        st.device_open = false;
+                        st.record_error(RuntimeError::DeviceDisconnected);
+                    }
+                    warn!("Audio device disconnected");
+                    send_event(&state.events, &state.status, AudioEvent::DeviceLost);
+                }
+            }
+
+            forward_engine_events(engine, &state.events, &state.status);
+        }
+        update_queue_stats(&state.status, queue);
+
+        if last_voice_snapshot.elapsed() >= VOICE_SNAPSHOT_INTERVAL {
+            last_voice_snapshot = Instant::now();
+            let snapshot = state
+                .engine
+                .as_ref()
+                .map(OpenalEngine::voice_snapshot)
+                .unwrap_or_default();
+            if let Ok(mut published) = voices.lock() {
+                *published = Arc::new(snapshot);
+            }
+        }
+
+        let Some(batch) = queue.pop_batch(Duration::from_millis(5)) else {
+            debug!("Audio runtime shutting down");
+            break;
+        };
+
+        for QueuedCommand { request, ack } in batch {
+            let result = state.apply(request);
+            if let Some(ack) = ack {
+                ack(result);
+            }
+        }
+    }
+
+    if let Some(engine) = state.engine.as_mut() {
+        engine.shutdown();
+    }
+    info!("Audio runtime stopped");
+}
+
+/// The active loop as the audio thread last asked for it, replayed on every new engine.
+#[derive(Clone, Copy, Debug)]
+struct LoopState {
+    voice: VoiceId,
+    key: BufferKey,
+    params: PlayOneShotParams,
+    paused: bool,
+}
+
+impl LoopState {
+    /// Follows a pause or resume of the voices matched by `filter`.
+    fn set_paused_if(&mut self, filter: VoiceFilter, paused: bool) {
+        if filter.matches(self.params.bus) {
+            self.paused = paused;
+        }
+    }
+}
+
+/// Everything the audio thread owns between commands, including what it needs to rebuild the
+/// engine after a render-mode change or restart.
+struct AudioThreadState {
+    config: AudioRuntimeConfig,
+    render_mode: AudioRenderMode,
+    mix: MasterMix,
+    device_paused: bool,
+    distance_model: DistanceModel,
+    buffers: HashMap<BufferKey, DecodedAudioMono16>,
+    loop_state: Option<LoopState>,
+    last_listener: ListenerFrame,
+    device_lost: bool,
+    engine: Option<OpenalEngine>,
+    events: mpsc::SyncSender<AudioEvent>,
+    status: Arc<Mutex<AudioRuntimeStatus>>,
+}
+
+impl AudioThreadState {
+    /// Applies one request and reports whether it took effect on the current engine.
+    fn apply(&mut self, request: Request) -> Result<CommandOutput, RuntimeError> {
+        match request {
+            Request::VoiceOffset(voice) => {
+                let engine = self.engine.as_ref().ok_or(RuntimeError::NotAvailable)?;
+                Ok(CommandOutput::Offset(engine.voice_offset(voice)?))
+            }
+            Request::Command(command) => self.apply_change(command).map(|()| CommandOutput::Done),
         }
+    }
 
-        match rx.recv_timeout(Duration::from_millis(5)) {
-            Ok(AudioCommand::Shutdown) => {
-                debug!("Audio runtime shutting down");
-                break;
+    fn apply_change(&mut self, command: AudioCommand) -> Result<(), RuntimeError> {
+        match command {
+            AudioCommand::SetMuted(value) => {
+                self.mix.muted = value;
+                let engine = self.engine.as_ref().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_master_gain(self.mix.gain());
+                self.report(result)
+            }
+            AudioCommand::SetMasterVolume(volume) => {
+                self.mix.volume = sanitize_volume(volume);
+                let engine = self.engine.as_ref().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_master_gain(self.mix.gain());
+                self.report(result)
+            }
+            AudioCommand::SetPitchScale(scale) => {
+                let scale = sanitize_pitch(scale);
+                self.mix.pitch_scale = scale;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_pitch_scale(scale);
+                self.report(result)
+            }
+            AudioCommand::SetBusVolume { bus, volume } => {
+                let volume = sanitize_volume(volume);
+                self.mix.buses.set(bus, volume);
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_bus_gain(bus, volume);
+                self.report(result)
             }
-            Ok(AudioCommand::SetMuted(value)) => {
-                muted = value;
-                if let Some(engine) = engine.as_ref() {
-                    if let Err(err) = engine.set_muted(muted) {
-                        update_status_error(&status, render_mode, distance_model, muted, &err);
-                    } else {
-                        update_status_ok(&status, render_mode, muted, engine);
+            AudioCommand::SetRenderMode(mode) => {
+                self.render_mode = mode;
+                if self.engine.is_some() {
+                    self.recreate_engine()
+                } else {
+                    self.start_engine("Audio runtime panicked during restart")
+                }
+            }
+            AudioCommand::SetMaxSources(max_sources) => {
+                self.config.max_sources = max_sources;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.plan_pool_resize(max_sources) {
+                    PoolResize::Reopen => self.recreate_engine(),
+                    PoolResize::InPlace(target) => {
+                        engine.resize_source_pool(max_sources, target);
+                        self.report(Ok(()))
                     }
                 }
             }
-            Ok(AudioCommand::SetRenderMode(mode)) => {
-                render_mode = mode;
-                let mut drop_engine = false;
-                match engine.as_mut() {
+            AudioCommand::SetPreferredDevice(device) => {
+                self.config.preferred_device = device;
+                if self.engine.is_some() {
+                    self.recreate_engine()
+                } else {
+                    self.start_engine("Audio runtime panicked while opening the device")
+                }
+            }
+            AudioCommand::SetHrtfDataset(dataset) => {
+                self.config.hrtf_dataset = dataset;
+                if self.engine.is_some() {
+                    self.recreate_engine()
+                } else {
+                    self.start_engine("Audio runtime panicked during restart")
+                }
+            }
+            AudioCommand::SetDistanceModel(model) => {
+                self.distance_model = model;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_distance_model(model);
+                self.report(result)
+            }
+            AudioCommand::SetListener(listener) => {
+                self.last_listener = listener;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                engine.set_listener(listener).map_err(|err| self.fail(err))
+            }
+            AudioCommand::SetVoiceTransform {
+                voice,
+                position,
+                velocity,
+            } => {
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.set_voice_transform(voice, position, velocity) {
+                    Ok(()) => Ok(()),
+                    // The voice may have finished while the transform was in flight.
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
+                }
+            }
+            AudioCommand::CreateBuffer { key, decoded } => {
+                let Some(engine) = self.engine.as_mut() else {
+                    self.buffers.insert(key, decoded);
+                    return Err(RuntimeError::NotAvailable);
+                };
+                let result = engine.create_buffer(key, &decoded);
+                if result.is_ok() {
+                    self.buffers.insert(key, decoded);
+                }
+                self.report(result)
+            }
+            AudioCommand::ReplaceBuffer { key, decoded } => {
+                let Some(engine) = self.engine.as_mut() else {
+                    self.buffers.insert(key, decoded);
+                    return Err(RuntimeError::NotAvailable);
+                };
+                // Once uploaded the engine plays the new PCM even if restarting voices failed,
+                // so the rebuild cache has to follow.
+                let result = engine.replace_buffer(key, &decoded);
+                if result.is_ok() {
+                    self.buffers.insert(key, decoded);
+                }
+                self.report(result.and_then(|reattached| reattached))
+            }
+            AudioCommand::DeleteBuffer { key } => {
+                self.buffers.remove(&key);
+                if self.loop_state.is_some_and(|state| state.key == key) {
+                    self.loop_state = None;
+                }
+                match self.engine.as_mut() {
                     Some(engine) => {
-                        let recreated = panic::catch_unwind(AssertUnwindSafe(|| {
-                            engine.recreate(render_mode, preferred_device, distance_model)
-                        }));
-                        match recreated {
-                            Ok(Ok(())) => {
-                                rebuild_buffers(
-                                    engine,
-                                    &buffers,
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                );
-                                rebuild_loop(
-                                    engine,
-                                    &loop_state,
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                );
-                                let _ = engine.set_muted(muted);
-                                let _ = engine.set_listener(last_listener);
-                                update_status_ok(&status, render_mode, muted, engine);
-                                info!(render_mode = %render_mode.as_str(), "Audio render mode changed");
-                            }
-                            Ok(Err(err)) => {
-                                update_status_error(
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                    &err,
-                                );
-                            }
-                            Err(panic) => {
-                                let message = panic_message(panic);
-                                update_status_panic(
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                    &message,
-                                );
-                                error!(panic = %message, "Audio runtime panicked while recreating");
-                                engine.shutdown();
-                                drop_engine = true;
-                            }
-                        }
-                    }
-                    None => {
-                        let recreated = panic::catch_unwind(AssertUnwindSafe(|| {
-                            OpenalEngine::new(
-                                render_mode,
-                                preferred_device,
-                                config.max_sources,
-                                distance_model,
-                            )
-                        }));
-                        match recreated {
-                            Ok(Ok(new_engine)) => {
-                                let mut new_engine = new_engine;
-                                rebuild_buffers(
-                                    &mut new_engine,
-                                    &buffers,
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                );
-                                rebuild_loop(
-                                    &mut new_engine,
-                                    &loop_state,
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                );
-                                let _ = new_engine.set_muted(muted);
-                                let _ = new_engine.set_listener(last_listener);
-                                update_status_ok(&status, render_mode, muted, &new_engine);
-                                info!(render_mode = %render_mode.as_str(), "Audio runtime started");
-                                engine = Some(new_engine);
-                            }
-                            Ok(Err(err)) => {
-                                update_status_error(
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                    &err,
-                                );
-                            }
-                            Err(panic) => {
-                                let message = panic_message(panic);
-                                update_status_panic(
-                                    &status,
-                                    render_mode,
-                                    distance_model,
-                                    muted,
-                                    &message,
-                                );
-                                error!(panic = %message, "Audio runtime panicked during restart");
-                            }
-                        }
+                        let result = engine.delete_buffer(key);
+                        self.report(result)
                     }
+                    None => Ok(()),
+                }
+            }
+            AudioCommand::StartLoop { voice, key, params } => {
+                self.loop_state = Some(LoopState {
+                    voice,
+                    key,
+                    params,
+                    paused: false,
+                });
+                let result = match self.engine.as_mut() {
+                    Some(engine) => engine.start_loop(voice, key, params).map_err(Into::into),
+                    None => Err(RuntimeError::NotAvailable),
+                };
+                self.report_play(voice, key, result)
+            }
+            AudioCommand::StopLoop => {
+                self.loop_state = None;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.stop_loop(STOP_LOOP_FADE);
+                self.report(result)
+            }
+            AudioCommand::StopVoice { voice, fade_out } => {
+                if self.loop_state.is_some_and(|state| state.voice == voice) {
+                    self.loop_state = None;
                 }
-                if drop_engine {
-                    engine = None;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.stop_voice(voice, fade_out) {
+                    Ok(()) => Ok(()),
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
                 }
             }
-            Ok(AudioCommand::SetDistanceModel(model)) => {
-                distance_model = model;
-                if let Some(engine) = engine.as_mut() {
-                    if let Err(err) = engine.set_distance_model(distance_model) {
-                        update_status_error(&status, render_mode, distance_model, muted, &err);
-                    } else {
-                        update_status_ok(&status, render_mode, muted, engine);
-                    }
+            AudioCommand::FadeVoice {
+                voice,
+                gain,
+                duration,
+            } => {
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.fade_voice(voice, gain, duration) {
+                    Ok(()) => Ok(()),
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
                 }
             }
-            Ok(AudioCommand::SetListener(listener)) => {
-                last_listener = listener;
-                if let Some(engine) = engine.as_ref() {
-                    if let Err(err) = engine.set_listener(listener) {
-                        update_status_error(&status, render_mode, distance_model, muted, &err);
-                    }
+            AudioCommand::SetVoiceOffset { voice, offset } => {
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.set_voice_offset(voice, offset) {
+                    Ok(()) => Ok(()),
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
                 }
             }
-            Ok(AudioCommand::CreateBuffer { key, decoded }) => {
-                buffers.insert(key, decoded.clone());
-                if let Some(engine) = engine.as_mut() {
-                    if let Err(err) = engine.create_buffer(key, &decoded) {
-                        update_status_error(&status, render_mode, distance_model, muted, &err);
-                    } else {
-                        update_status_ok(&status, render_mode, muted, engine);
-                    }
+            AudioCommand::PauseVoice { voice } => {
+                self.set_loop_paused(voice, true);
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.pause_voice(voice) {
+                    Ok(()) => Ok(()),
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
                 }
             }
-            Ok(AudioCommand::StartLoop { key, params }) => {
-                loop_state = Some((key, params));
-                if let Some(engine) = engine.as_mut() {
-                    if let Err(err) = engine.start_loop(key, params) {
-                        if let Ok(mut st) = status.lock() {
-                            st.last_error = Some(err.to_string());
-                        }
-                    } else {
-                        update_status_ok(&status, render_mode, muted, engine);
-                    }
+            AudioCommand::ResumeVoice { voice } => {
+                self.set_loop_paused(voice, false);
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                match engine.resume_voice(voice) {
+                    Ok(()) => Ok(()),
+                    Err(err @ OpenalError::VoiceMissing(_)) => Err(err.into()),
+                    Err(err) => Err(self.fail(err)),
                 }
             }
-            Ok(AudioCommand::StopLoop) => {
-                loop_state = None;
-                if let Some(engine) = engine.as_mut() {
-                    if let Err(err) = engine.stop_loop() {
-                        if let Ok(mut st) = status.lock() {
-                            st.last_error = Some(err.to_string());
-                        }
-                    } else {
-                        update_status_ok(&status, render_mode, muted, engine);
-                    }
+            AudioCommand::PauseVoices(filter) => {
+                if let Some(state) = self.loop_state.as_mut() {
+                    state.set_paused_if(filter, true);
                 }
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_voices_paused(filter, true).map(drop);
+                self.report(result)
             }
-            Ok(AudioCommand::PlayOneShot { key, params }) => {
-                if let Some(engine) = engine.as_mut() {
-                    if let Err(err) = engine.play_one_shot(key, params) {
-                        if let Ok(mut st) = status.lock() {
-                            st.last_error = Some(err.to_string());
-                        }
+            AudioCommand::ResumeVoices(filter) => {
+                if let Some(state) = self.loop_state.as_mut() {
+                    state.set_paused_if(filter, false);
+                }
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_voices_paused(filter, false).map(drop);
+                self.report(result)
+            }
+            AudioCommand::SetDevicePaused(paused) => {
+                self.device_paused = paused;
+                let engine = self.engine.as_mut().ok_or(RuntimeError::NotAvailable)?;
+                let result = engine.set_device_paused(paused);
+                if result.is_ok() {
+                    if let Ok(mut st) = self.status.lock() {
+                        st.device_paused = paused;
                     }
                 }
+                self.report(result)
             }
-            Err(mpsc::RecvTimeoutError::Timeout) => {}
-            Err(mpsc::RecvTimeoutError::Disconnected) => {
-                break;
+            AudioCommand::PlayOneShot { voice, key, params } => {
+                let result = match self.engine.as_mut() {
+                    Some(engine) => engine.play_one_shot(voice, key, params).map_err(Into::into),
+                    None => Err(RuntimeError::NotAvailable),
+                };
+                self.report_play(voice, key, result)
             }
         }
     }
 
-    if let Some(engine) = engine.as_mut() {
+    /// Records an engine failure in the status and converts it for the command's submitter.
+    fn fail(&self, err: OpenalError) -> RuntimeError {
+        update_status_error(
+            &self.status,
+            self.render_mode,
+            self.distance_model,
+            self.mix,
+            &err,
+        );
+        err.into()
+    }
+
+    fn report(&self, result: Result<(), OpenalError>) -> Result<(), RuntimeError> {
+        match result {
+            Ok(()) => {
+                if let Some(engine) = self.engine.as_ref() {
+                    update_status_ok(&self.status, self.render_mode, self.mix, engine);
+                }
+                Ok(())
+            }
+            Err(err) => Err(self.fail(err)),
+        }
+    }
+
+    fn report_play(
+        &self,
+        voice: VoiceId,
+        key: BufferKey,
+        result: Result<(), RuntimeError>,
+    ) -> Result<(), RuntimeError> {
+        match &result {
+            Ok(()) => send_event(
+                &self.events,
+                &self.status,
+                AudioEvent::VoiceStarted { voice, key },
+            ),
+            Err(err) => {
+                if let RuntimeError::Openal(_) = err {
+                    record_error(&self.status, err.clone());
+                }
+                send_play_failed(&self.events, &self.status, voice, key, err.to_string());
+            }
+        }
+        result
+    }
+
+    fn start_engine(&mut self, panic_context: &str) -> Result<(), RuntimeError> {
+        let created = panic::catch_unwind(AssertUnwindSafe(|| {
+            OpenalEngine::new(
+                self.render_mode,
+                self.config.hrtf_dataset.as_deref(),
+                self.config.preferred_device.as_deref(),
+                self.config.max_sources,
+                self.distance_model,
+            )
+        }));
+        match created {
+            Ok(Ok(engine)) => {
+                self.engine = Some(engine);
+                self.restore_engine_state();
+                info!(render_mode = %self.render_mode.as_str(), "Audio runtime started");
+                Ok(())
+            }
+            Ok(Err(err)) => {
+                error!(error = %err, "Audio runtime failed to initialize");
+                Err(self.fail(err))
+            }
+            Err(panic) => {
+                let message = panic_message(panic);
+                self.record_panic(&message);
+                error!(panic = %message, "{panic_context}");
+                Err(RuntimeError::Panicked(message))
+            }
+        }
+    }
+
+    fn recreate_engine(&mut self) -> Result<(), RuntimeError> {
+        let Some(engine) = self.engine.as_mut() else {
+            return Err(RuntimeError::NotAvailable);
+        };
+        let (render_mode, distance_model) = (self.render_mode, self.distance_model);
+        let hrtf_dataset = self.config.hrtf_dataset.as_deref();
+        let preferred_device = self.config.preferred_device.as_deref();
+        let max_sources = self.config.max_sources;
+        let recreated = panic::catch_unwind(AssertUnwindSafe(|| {
+            engine.recreate(
+                render_mode,
+                hrtf_dataset,
+                preferred_device,
+                max_sources,
+                distance_model,
+            )
+        }));
+        match recreated {
+            Ok(Ok(())) => {
+                self.restore_engine_state();
+                info!(
+                    render_mode = %self.render_mode.as_str(),
+                    device = ?self.config.preferred_device,
+                    max_sources,
+                    "Audio device reopened"
+                );
+                Ok(())
+            }
+            Ok(Err(err)) => {
+                error!(error = %err, "Audio device failed to reopen");
+                self.drop_engine();
+                Err(self.fail(err))
+            }
+            Err(panic) => {
+                let message = panic_message(panic);
+                self.drop_engine();
+                self.record_panic(&message);
+                error!(panic = %message, "Audio runtime panicked while recreating");
+                Err(RuntimeError::Panicked(message))
+            }
+        }
+    }
+
+    fn set_loop_paused(&mut self, voice: VoiceId, paused: bool) {
+        if let Some(state) = self
+            .loop_state
+            .as_mut()
+            .filter(|state| state.voice == voice)
+        {
+            state.paused = paused;
+        }
+    }
+
+    /// Discards an engine whose reopen failed so the next device command starts a fresh one.
+    fn drop_engine(&mut self) {
+        let Some(mut engine) = self.engine.take() else {
+            return;
+        };
+        // Delivers the `VoiceDropped` events queued before the old device closed.
+        forward_engine_events(&mut engine, &self.events, &self.status);
         engine.shutdown();
+        if let Ok(mut st) = self.status.lock() {
+            st.record_device_closed();
+        }
+    }
+
+    /// Re-uploads cached buffers, restarts (and re-pauses) the loop and reapplies mute/listener on a fresh
+    /// context.
+    fn restore_engine_state(&mut self) {
+        let Some(engine) = self.engine.as_mut() else {
+            return;
+        };
+        let mut failures = Vec::new();
+        for (key, decoded) in &self.buffers {
+            if let Err(err) = engine.create_buffer(*key, decoded) {
+                failures.push(err);
+            }
+        }
+        if let Some(state) = self.loop_state {
+            let restarted = engine.start_loop(state.voice, state.key, state.params);
+            let restored = match restarted {
+                Ok(()) if state.paused => engine.pause_voice(state.voice),
+                other => other,
+            };
+            if let Err(err) = restored {
+                failures.push(err);
+            }
+        }
+        let _ = engine.set_master_gain(self.mix.gain());
+        if let Err(err) = engine.set_pitch_scale(self.mix.pitch_scale) {
+            failures.push(err);
+        }
+        for bus in AudioBus::ALL {
+            if let Err(err) = engine.set_bus_gain(bus, self.mix.buses.get(bus)) {
+                failures.push(err);
+            }
+        }
+        if self.device_paused {
+            if let Err(err) = engine.set_device_paused(true) {
+                failures.push(err);
+            }
+        }
+        let _ = engine.set_listener(self.last_listener);
+        self.device_lost = false;
+        for err in failures {
+            self.fail(err);
+        }
+        if let Some(engine) = self.engine.as_ref() {
+            update_status_ok(&self.status, self.render_mode, self.mix, engine);
+        }
+    }
+
+    fn record_panic(&self, message: &str) {
+        update_status_panic(
+            &self.status,
+            self.render_mode,
+            self.distance_model,
+            self.mix,
+            message,
+        );
+    }
+}
+
+fn record_error(status: &Arc<Mutex<AudioRuntimeStatus>>, error: RuntimeError) {
+    if let Ok(mut st) = status.lock() {
+        st.record_error(error);
+    }
+}
+
+pub(crate) fn send_event(
+    events: &mpsc::SyncSender<AudioEvent>,
+    status: &Arc<Mutex<AudioRuntimeStatus>>,
+    event: AudioEvent,
+) {
+    if let Err(mpsc::TrySendError::Full(event)) = events.try_send(event) {
+        debug!(event = ?event, "Audio event queue full; dropping event");
+        if let Ok(mut st) = status.lock() {
+            st.events_dropped += 1;
+        }
+    }
+}
+
+fn send_play_failed(
+    events: &mpsc::SyncSender<AudioEvent>,
+    status: &Arc<Mutex<AudioRuntimeStatus>>,
+    voice: VoiceId,
+    key: BufferKey,
+    reason: String,
+) {
+    send_event(
+        events,
+        status,
+        AudioEvent::PlayFailed { voice, key, reason },
+    );
+}
+
+fn forward_engine_events(
+    engine: &mut OpenalEngine,
+    events: &mpsc::SyncSender<AudioEvent>,
+    status: &Arc<Mutex<AudioRuntimeStatus>>,
+) {
+    for event in engine.drain_events() {
+        send_event(events, status, event);
     }
-    info!("Audio runtime stopped");
 }
 
 fn update_counts(status: &Arc<Mutex<AudioRuntimeStatus>>, engine: &OpenalEngine) {
@@ -583,42 +1816,78 @@ fn update_counts(status: &Arc<Mutex<AudioRuntimeStatus>>, engine: &OpenalEngine)
     };
     st.loaded_buffers = engine.loaded_buffers();
     st.active_sources = engine.active_sources();
+    st.source_pool_size = engine.source_pool_size();
 }
 
-fn rebuild_buffers(
-    engine: &mut OpenalEngine,
-    buffers: &HashMap<BufferKey, DecodedAudioMono16>,
-    status: &Arc<Mutex<AudioRuntimeStatus>>,
-    render_mode: AudioRenderMode,
-    distance_model: DistanceModel,
+fn update_queue_stats(status: &Arc<Mutex<AudioRuntimeStatus>>, queue: &CommandQueue) {
+    let stats = queue.stats();
+    let Ok(mut st) = status.lock() else {
+        return;
+    };
+    st.command_queue_depth = stats.depth;
+    st.commands_coalesced = stats.coalesced;
+    st.commands_dropped = stats.dropped;
+}
+
+/// Listener-wide output controls the audio thread reapplies to every new engine.
+#[derive(Clone, Copy, Debug)]
+struct MasterMix {
     muted: bool,
-) {
-    for (key, decoded) in buffers {
-        if let Err(err) = engine.create_buffer(*key, decoded) {
-            update_status_error(status, render_mode, distance_model, muted, &err);
+    volume: f32,
+    pitch_scale: f32,
+    buses: BusVolumes,
+}
+
+impl Default for MasterMix {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            volume: 1.0,
+            pitch_scale: 1.0,
+            buses: BusVolumes::default(),
         }
     }
 }
 
-fn rebuild_loop(
-    engine: &mut OpenalEngine,
-    loop_state: &Option<(BufferKey, PlayOneShotParams)>,
-    status: &Arc<Mutex<AudioRuntimeStatus>>,
-    render_mode: AudioRenderMode,
-    distance_model: DistanceModel,
-    muted: bool,
-) {
-    if let Some((key, params)) = loop_state {
-        if let Err(err) = engine.start_loop(*key, *params) {
-            update_status_error(status, render_mode, distance_model, muted, &err);
+impl MasterMix {
+    fn gain(self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume
         }
     }
+
+    fn record(self, st: &mut AudioRuntimeStatus) {
+        st.muted = self.muted;
+        st.master_volume = self.volume;
+        st.pitch_scale = self.pitch_scale;
+        st.bus_volumes = self.buses;
+    }
+}
+
+fn sanitize_volume(volume: f32) -> f32 {
+    if volume.is_finite() {
+        volume.max(0.0)
+    } else {
+        1.0
+    }
+}
+
+/// Converts decibels to a linear gain (0 dB = 1.0, -6 dB ≈ 0.5).
+pub fn gain_from_db(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Converts a linear gain to decibels; silence is negative infinity.
+pub fn db_from_gain(gain: f32) -> f32 {
+    20.0 * gain.log10()
 }
 
 fn update_status_ok(
     status: &Arc<Mutex<AudioRuntimeStatus>>,
     render_mode: AudioRenderMode,
-    muted: bool,
+    mix: MasterMix,
     engine: &OpenalEngine,
 ) {
     let Ok(mut st) = status.lock() else {
@@ -630,19 +1899,26 @@ fn update_status_ok(
     st.device_open = true;
     st.context_created = true;
     st.render_mode = render_mode;
+    st.output_mode_requested = engine.output_mode_requested().map(str::to_string);
     st.output_mode = output_mode_name.map(|s| s.to_string());
     st.output_mode_raw = output_mode_raw;
     st.distance_model = engine_distance_model;
     st.hrtf_active = hrtf_active;
-    st.muted = muted;
-    st.last_error = None;
+    if st.hrtf_datasets != engine.hrtf_datasets() {
+        st.hrtf_datasets = engine.hrtf_datasets().to_vec();
+    }
+    if st.hrtf_dataset.as_deref() != engine.hrtf_dataset() {
+        st.hrtf_dataset = engine.hrtf_dataset().map(str::to_string);
+    }
+    st.device_sample_rate_hz = engine.output_sample_rate_hz();
+    mix.record(&mut st);
 }
 
 fn update_status_error(
     status: &Arc<Mutex<AudioRuntimeStatus>>,
     render_mode: AudioRenderMode,
     distance_model: DistanceModel,
-    muted: bool,
+    mix: MasterMix,
     err: &OpenalError,
 ) {
     let Ok(mut st) = status.lock() else {
@@ -650,15 +1926,15 @@ fn update_status_error(
     };
     st.render_mode = render_mode;
     st.distance_model = distance_model;
-    st.muted = muted;
-    st.last_error = Some(err.to_string());
+    mix.record(&mut st);
+    st.record_error(RuntimeError::Openal(err.clone()));
 }
 
 fn update_status_panic(
     status: &Arc<Mutex<AudioRuntimeStatus>>,
     render_mode: AudioRenderMode,
     distance_model: DistanceModel,
-    muted: bool,
+    mix: MasterMix,
     message: &str,
 ) {
     let Ok(mut st) = status.lock() else {
@@ -666,8 +1942,8 @@ fn update_status_panic(
     };
     st.render_mode = render_mode;
     st.distance_model = distance_model;
-    st.muted = muted;
-    st.last_error = Some(format!("panic: {message}"));
+    mix.record(&mut st);
+    st.record_error(RuntimeError::Panicked(message.to_string()));
 }
 
 fn panic_message(panic: Box<dyn Any + Send>) -> String {
@@ -682,7 +1958,116 @@ fn panic_message(panic: Box<dyn Any + Send>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::AudioRenderMode;
+    use super::{
+        db_from_gain, gain_from_db, send_event, AudioBus, AudioEvent, AudioRenderMode,
+        AudioRuntimeStatus, AudioTicket, LoopState, OutputLayout, PlayOneShotParams, RuntimeError,
+        VoiceFilter,
+    };
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn full_event_queue_drops_and_counts_new_events() {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let status = Arc::new(Mutex::new(AudioRuntimeStatus::default()));
+        send_event(&tx, &status, AudioEvent::DeviceLost);
+        send_event(&tx, &status, AudioEvent::BufferReady { key: 1 });
+
+        assert_eq!(status.lock().unwrap().events_dropped, 1);
+        assert!(matches!(rx.try_recv(), Ok(AudioEvent::DeviceLost)));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn failed_reopen_clears_the_device_but_keeps_the_mix() {
+        let mut status = AudioRuntimeStatus {
+            library_loaded: true,
+            device_open: true,
+            context_created: true,
+            output_mode: Some("Stereo".to_string()),
+            device_sample_rate_hz: Some(48_000),
+            hrtf_active: true,
+            hrtf_datasets: vec!["Built-In".to_string()],
+            hrtf_dataset: Some("Built-In".to_string()),
+            master_volume: 0.5,
+            loaded_buffers: 3,
+            active_sources: 2,
+            source_pool_size: 32,
+            ..AudioRuntimeStatus::default()
+        };
+        status.record_device_closed();
+
+        assert!(status.library_loaded);
+        assert!(!status.device_open);
+        assert!(!status.context_created);
+        assert_eq!(status.output_mode, None);
+        assert_eq!(status.device_sample_rate_hz, None);
+        assert!(!status.hrtf_active);
+        assert!(status.hrtf_datasets.is_empty());
+        assert_eq!(status.hrtf_dataset, None);
+        assert_eq!(status.master_volume, 0.5);
+        assert_eq!(
+            (
+                status.loaded_buffers,
+                status.active_sources,
+                status.source_pool_size
+            ),
+            (0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn loop_state_follows_matching_pauses() {
+        let mut state = LoopState {
+            voice: 7,
+            key: 1,
+            params: PlayOneShotParams {
+                bus: AudioBus::Music,
+                ..Default::default()
+            },
+            paused: false,
+        };
+        state.set_paused_if(VoiceFilter::ExceptBus(AudioBus::Ui), true);
+        assert!(state.paused);
+        state.set_paused_if(VoiceFilter::Bus(AudioBus::Sfx), false);
+        assert!(state.paused);
+        state.set_paused_if(VoiceFilter::All, false);
+        assert!(!state.paused);
+    }
+
+    #[test]
+    fn voice_filter_matches_buses() {
+        assert!(VoiceFilter::All.matches(AudioBus::Ui));
+        assert!(VoiceFilter::Bus(AudioBus::Music).matches(AudioBus::Music));
+        assert!(!VoiceFilter::Bus(AudioBus::Music).matches(AudioBus::Sfx));
+        assert!(VoiceFilter::ExceptBus(AudioBus::Ui).matches(AudioBus::Ambience));
+        assert!(!VoiceFilter::ExceptBus(AudioBus::Ui).matches(AudioBus::Ui));
+    }
+
+    #[test]
+    fn db_and_gain_convert_both_ways() {
+        assert_eq!(gain_from_db(0.0), 1.0);
+        assert!((gain_from_db(-20.0) - 0.1).abs() < 1e-6);
+        assert!((db_from_gain(0.5) + 6.0206).abs() < 1e-3);
+        assert_eq!(db_from_gain(0.0), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn ticket_reports_result_or_stopped_thread() {
+        let (ack, completion) = mpsc::sync_channel(1);
+        let ticket: AudioTicket = AudioTicket { completion };
+        assert!(ticket.try_result().is_none());
+        ack.send(Err(RuntimeError::QueueFull)).unwrap();
+        assert!(matches!(
+            ticket.wait_timeout(Duration::ZERO),
+            Some(Err(RuntimeError::QueueFull))
+        ));
+
+        let (ack, completion) = mpsc::sync_channel::<Result<(), RuntimeError>>(1);
+        drop(ack);
+        let ticket = AudioTicket { completion };
+        assert!(matches!(ticket.wait(), Err(RuntimeError::ThreadStopped)));
+    }
 
     #[test]
     fn parse_render_modes() {
@@ -711,6 +2096,14 @@ mod tests {
             AudioRenderMode::parse("surround-auto"),
             Some(AudioRenderMode::SurroundAuto)
         );
+        assert_eq!(
+            AudioRenderMode::parse("7.1"),
+            Some(AudioRenderMode::Layout(OutputLayout::Surround71))
+        );
+        assert_eq!(
+            AudioRenderMode::parse("uhj"),
+            Some(AudioRenderMode::Layout(OutputLayout::StereoUhj))
+        );
         assert_eq!(AudioRenderMode::parse("nope"), None);
     }
 }
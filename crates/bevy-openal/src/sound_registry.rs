@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use thiserror::Error;
+
+use crate::BufferKey;
+
+/// One past the last key the registry hands out; asset buffers (`ASSET_BUFFER_KEY_BASE`) start
+/// here.
+const KEY_END: BufferKey = 0x8000_0000;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SoundRegistryError {
+    #[error("sound name `{name}` is already registered as buffer {key}")]
+    NameTaken { name: String, key: BufferKey },
+    #[error("buffer {key} is already registered as `{name}`")]
+    KeyTaken { key: BufferKey, name: String },
+    #[error("buffer key {0} is outside the registry's range (1..0x8000_0000)")]
+    KeyOutOfRange(BufferKey),
+    #[error("unknown sound name `{0}`")]
+    UnknownName(String),
+    #[error("no free buffer keys left")]
+    KeysExhausted,
+}
+
+/// Names for runtime buffers.
+///
+/// Hands out [`BufferKey`]s (from 1, below the range reserved for audio assets) and maps names to
+/// them both ways, so playback can say `OpenalSound::Named("beep")` instead of carrying keys
+/// around. The registry only tracks names: create the buffer under the returned key, and free it
+/// with `AudioRuntime::delete_buffer` after [`Self::remove`].
+#[derive(Resource, Debug)]
+pub struct SoundRegistry {
+    next_key: BufferKey,
+    keys: HashMap<String, BufferKey>,
+    names: HashMap<BufferKey, String>,
+}
+
+impl Default for SoundRegistry {
+    fn default() -> Self {
+        Self {
+            next_key: 1,
+            keys: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+}
+
+impl SoundRegistry {
+    /// Allocates a fresh key for `name`.
+    pub fn register(&mut self, name: impl Into<String>) -> Result<BufferKey, SoundRegistryError> {
+        let name = name.into();
+        if let Some(&key) = self.keys.get(&name) {
+            return Err(SoundRegistryError::NameTaken { name, key });
+        }
+        let key = self.allocate_key()?;
+        self.keys.insert(name.clone(), key);
+        self.names.insert(key, name);
+        Ok(key)
+    }
+
+    /// Names a key chosen elsewhere. Neither the name nor the key may be registered already, and
+    /// the key must be below the asset key range (and not 0).
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        key: BufferKey,
+    ) -> Result<(), SoundRegistryError> {
+        if key == 0 || key >= KEY_END {
+            return Err(SoundRegistryError::KeyOutOfRange(key));
+        }
+        let name = name.into();
+        if let Some(&key) = self.keys.get(&name) {
+            return Err(SoundRegistryError::NameTaken { name, key });
+        }
+        if let Some(name) = self.names.get(&key) {
+            return Err(SoundRegistryError::KeyTaken {
+                key,
+                name: name.clone(),
+            });
+        }
+        self.keys.insert(name.clone(), key);
+        self.names.insert(key, name);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<BufferKey> {
+        self.keys.get(name).copied()
+    }
+
+    /// Like [`Self::get`], but an unknown name is an error.
+    pub fn key(&self, name: &str) -> Result<BufferKey, SoundRegistryError> {
+        self.get(name)
+            .ok_or_else(|| SoundRegistryError::UnknownName(name.to_string()))
+    }
+
+    pub fn name(&self, key: BufferKey) -> Option<&str> {
+        self.names.get(&key).map(String::as_str)
+    }
+
+    /// Forgets `name`, returning its key; the key may be handed out again later.
+    pub fn remove(&mut self, name: &str) -> Option<BufferKey> {
+        let key = self.keys.remove(name)?;
+        self.names.remove(&key);
+        Some(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, BufferKey)> {
+        self.keys.iter().map(|(name, &key)| (name.as_str(), key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Next unused key, wrapping back to 1 at the asset key range.
+    fn allocate_key(&mut self) -> Result<BufferKey, SoundRegistryError> {
+        if self.names.len() >= (KEY_END - 1) as usize {
+            return Err(SoundRegistryError::KeysExhausted);
+        }
+        loop {
+            let key = self.next_key;
+            self.next_key = if key + 1 >= KEY_END { 1 } else { key + 1 };
+            if !self.names.contains_key(&key) {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SoundRegistry, SoundRegistryError, KEY_END};
+
+    #[test]
+    fn registers_names_and_rejects_collisions() {
+        let mut registry = SoundRegistry::default();
+        let beep = registry.register("beep").unwrap();
+        registry.insert("manual", 2).unwrap();
+        assert_eq!(
+            registry.register("beep"),
+            Err(SoundRegistryError::NameTaken {
+                name: "beep".to_string(),
+                key: beep,
+            })
+        );
+        assert_eq!(
+            registry.insert("other", 2),
+            Err(SoundRegistryError::KeyTaken {
+                key: 2,
+                name: "manual".to_string(),
+            })
+        );
+
+        // Key 2 is taken by hand, so allocation skips it.
+        assert_eq!(registry.register("boop"), Ok(3));
+        assert_eq!(registry.name(3), Some("boop"));
+
+        assert_eq!(registry.remove("beep"), Some(beep));
+        assert_eq!(
+            registry.key("beep"),
+            Err(SoundRegistryError::UnknownName("beep".to_string()))
+        );
+        assert_eq!(registry.name(beep), None);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn insert_rejects_keys_outside_its_range() {
+        let mut registry = SoundRegistry::default();
+        assert_eq!(
+            registry.insert("zero", 0),
+            Err(SoundRegistryError::KeyOutOfRange(0))
+        );
+        assert_eq!(
+            registry.insert("asset", KEY_END),
+            Err(SoundRegistryError::KeyOutOfRange(0x8000_0000))
+        );
+        assert!(registry.is_empty());
+        registry.insert("last", 0x7FFF_FFFF).unwrap();
+    }
+}